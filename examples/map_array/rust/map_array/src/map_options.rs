@@ -1,35 +1,27 @@
-use std::fmt;
-
 use lean::{
-    Minimal, Runtime,
-    lean_types::{Owner, object::Object, string::LeanString},
+    LeanStructure, Minimal, Runtime,
+    lean_types::{Owner, ctor::LeanStructure, string::LeanString},
 };
-use map_array_sys::MapArray::Basic_c::{map_options_to_string, mk_map_options};
+use map_array_sys::MapArray::Basic_c::map_options_to_string;
 
 use crate::MapArrayModule;
 
-pub struct MapOptions(Object<Self>);
-
-impl MapOptions {
-    pub fn new<R: Minimal, M: MapArrayModule>(
-        _runtime: &Runtime<R, M>,
-        addend: i32,
-        multiplicand: i32,
-    ) -> Self {
-        let lean_map_options = unsafe { mk_map_options(addend as u32, multiplicand as u32) };
-        Self(unsafe { Object::new(lean_map_options) })
-    }
-
-    pub fn into_inner(self) -> Object<Self> {
-        self.0
-    }
+/// The Rust counterpart of the Lean `MapOptions` structure
+#[derive(LeanStructure, Clone, Copy, Debug, PartialEq, Eq)]
+#[lean(num_objs = 0, num_usizes = 0, scalar_size = 8)]
+pub struct MapOptions {
+    #[lean(offset = 0)]
+    pub addend: i32,
+    #[lean(offset = 4)]
+    pub multiplicand: i32,
 }
 
-impl fmt::Display for MapOptions {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let clone = self.0.share();
+impl MapOptions {
+    /// Formats the options with their Lean `ToString` instance
+    pub fn to_lean_string<R: Minimal, M: MapArrayModule>(&self, runtime: &Runtime<R, M>) -> String {
+        let map_options = self.into_lean(runtime);
         let map_options_lean_string =
-            unsafe { LeanString::new(map_options_to_string(clone.into_raw())) };
-        f.write_str(map_options_lean_string.as_cstr().to_str().unwrap())
+            unsafe { LeanString::new(map_options_to_string(map_options.into_raw())) };
+        map_options_lean_string.into()
     }
 }
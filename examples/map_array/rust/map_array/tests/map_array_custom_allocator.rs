@@ -1,8 +1,10 @@
 #![forbid(unsafe_code)]
 
+use std::borrow::Borrow;
+
 use lean::{
     LeanError, LeanIoError, MimallocAllocator, MinimalComponents, Runtime,
-    RuntimeInitializationError,
+    RuntimeInitializationError, lean_types::ctor::LeanStructure,
 };
 use map_array::{MapArrayModuleInitializer, MapOptions};
 
@@ -19,9 +21,17 @@ fn map_array_custom_allocator()
         |runtime: &Runtime<MinimalComponents, MapArrayModuleInitializer>| {
             let addend: i32 = 2;
             let multiplicand: i32 = 3;
-            let map_options = MapOptions::new(runtime, addend, multiplicand);
-
-            let map_options_string = map_options.to_string();
+            let map_options = MapOptions {
+                addend,
+                multiplicand,
+            };
+            let lean_map_options = map_options.into_lean(runtime);
+            assert_eq!(
+                MapOptions::from_lean(lean_map_options.borrow()),
+                map_options
+            );
+
+            let map_options_string = map_options.to_lean_string(runtime);
             assert_eq!(&map_options_string, "{ addend := 2, multiplicand := 3 }");
 
             let mut array_data: [u8; 6] = Default::default();
@@ -22,9 +22,15 @@ fn main()
         |runtime: &Runtime<MinimalComponents, MapArrayModuleInitializer>| {
             let addend: i32 = 2;
             let multiplicand: i32 = 3;
-            let map_options = MapOptions::new(runtime, addend, multiplicand);
-
-            println!("MapOptions instance: {}", map_options);
+            let map_options = MapOptions {
+                addend,
+                multiplicand,
+            };
+
+            println!(
+                "MapOptions instance: {}",
+                map_options.to_lean_string(runtime)
+            );
 
             let mut array: [u8; 6] = Default::default();
             for (i, element) in array.iter_mut().enumerate() {
@@ -0,0 +1,324 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{Attribute, Data, DeriveInput, Ident, LitStr, spanned::Spanned};
+
+use crate::layout::{Brand, Ctor};
+
+/// Parses the name of the view enum from `#[lean(view = ...)]`
+fn parse_view_name(attrs: &[Attribute], name: &Ident) -> syn::Result<Ident> {
+    let mut view_name = format_ident!("{name}View");
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("lean")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("view") {
+                view_name = meta.value()?.parse()?;
+                Ok(())
+            } else {
+                Err(meta.error("expected `view` in `#[lean(...)]`"))
+            }
+        })?;
+    }
+    Ok(view_name)
+}
+
+pub fn impl_lean_inductive(input: TokenStream2) -> syn::Result<TokenStream2> {
+    let derive_input: DeriveInput = syn::parse2(input)?;
+    let name = &derive_input.ident;
+    let vis = &derive_input.vis;
+
+    let brand = Brand::new(
+        &derive_input.generics,
+        "`LeanInductive` can only be derived for enums whose only generic parameter is the \
+         lifetime of the runtime",
+    )?;
+    let variants = match &derive_input.data {
+        Data::Enum(data) => &data.variants,
+        _ => {
+            return Err(syn::Error::new(
+                name.span(),
+                "`LeanInductive` can only be derived for enums",
+            ));
+        }
+    };
+    if variants.is_empty() {
+        return Err(syn::Error::new(
+            name.span(),
+            "`LeanInductive` cannot be derived for enums without variants",
+        ));
+    }
+    if let Some(variant) = variants
+        .iter()
+        .find(|variant| variant.discriminant.is_some())
+    {
+        return Err(syn::Error::new(
+            variant.span(),
+            "constructor tags are assigned in declaration order, so variants cannot have \
+             explicit discriminants",
+        ));
+    }
+    // Lean represents structure-like types with a single relevant field as
+    // that field
+    if variants.len() == 1 && variants[0].fields.len() == 1 {
+        return Err(syn::Error::new(
+            variants[0].fields.span(),
+            "Lean represents inductive types with a single constructor and a single relevant \
+             field as that field, so `LeanInductive` requires more fields or constructors",
+        ));
+    }
+
+    let idents: Vec<&Ident> = variants.iter().map(|variant| &variant.ident).collect();
+    let tags: Vec<u32> = (0..variants.len() as u32).collect();
+    let ctors: Vec<Ctor> = variants
+        .iter()
+        .enumerate()
+        .map(|(i, variant)| Ctor::new(&variant.fields, &brand.lifetime, &format!("_{i}")))
+        .collect();
+    let layouts = variants
+        .iter()
+        .zip(&ctors)
+        .map(|(variant, ctor)| {
+            let ident = &variant.ident;
+            ctor.layout(&format!("`{name}::{ident}`"), &variant.attrs, &[])
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    let layout_consts = ctors.iter().map(Ctor::layout_const);
+    let invalid_tag_message = LitStr::new(
+        &format!("invalid constructor tag {{}} for `{name}`"),
+        name.span(),
+    );
+    let view_lifetime = brand.borrow_lifetime();
+    let Brand {
+        lifetime,
+        impl_generics,
+        ty_generics,
+    } = &brand;
+
+    let generated = if variants.iter().all(|variant| variant.fields.is_empty()) {
+        // Enumeration types are boxed scalars, but they are stored in
+        // constructor fields as unboxed scalars of the smallest sufficient
+        // size. Types with a single constructor are not enumeration types, so
+        // their fields are boxed like other objects, and always hold `box(0)`.
+        let repr = match variants.len() {
+            1 => None,
+            2..0x100 => Some(quote!(u8)),
+            0x100..0x10000 => Some(quote!(u16)),
+            _ => Some(quote!(u32)),
+        };
+        let (field_kind, field_read, field_write) = match &repr {
+            Some(repr) => (
+                quote!(<#repr as ::lean::lean_types::ctor::LeanField<'static>>::KIND),
+                quote! {
+                    from_tag(unsafe { <#repr as ::lean::lean_types::ctor::LeanField<#lifetime>>::read(ctor, location) }.into())
+                },
+                quote! {
+                    unsafe {
+                        <#repr as ::lean::lean_types::ctor::LeanField<#lifetime>>::write(to_tag(&self) as #repr, ctor, location)
+                    }
+                },
+            ),
+            None => (
+                quote!(::lean::lean_types::ctor::FieldKind::Object),
+                quote! {
+                    from_tag(unsafe { ::lean_sys::lean_unbox(::lean_sys::lean_ctor_get(ctor, location)) } as u32)
+                },
+                quote! {
+                    unsafe {
+                        ::lean_sys::lean_ctor_set(ctor, location, ::lean_sys::lean_box(to_tag(&self) as usize))
+                    }
+                },
+            ),
+        };
+        quote! {
+            const _: () = {
+                #(#layouts)*
+
+                fn from_tag(tag: u32) -> #name {
+                    match tag {
+                        #(#tags => #name::#idents,)*
+                        tag => panic!(#invalid_tag_message, tag),
+                    }
+                }
+
+                fn to_tag(value: &#name) -> u32 {
+                    match value {
+                        #(#name::#idents => #tags,)*
+                    }
+                }
+
+                unsafe impl #impl_generics ::lean::lean_types::ctor::LeanCtorTypeTag for #name #ty_generics {
+                    const LAYOUTS: &'static [::lean::lean_types::ctor::CtorLayout] = &[#(#layout_consts),*];
+                }
+
+                unsafe impl #impl_generics ::lean::lean_types::ctor::LeanInductive<#lifetime> for #name #ty_generics {
+                    type View<#view_lifetime>
+                        = Self
+                    where
+                        #lifetime: #view_lifetime;
+
+                    fn from_lean(object: &::lean::lean_types::object::Obj<#lifetime, Self>) -> Self {
+                        from_tag(unsafe {
+                            ::lean_sys::lean_obj_tag(::lean::lean_types::Reference::as_mut_raw(object))
+                        })
+                    }
+
+                    fn view(object: &::lean::lean_types::object::Obj<#lifetime, Self>) -> Self {
+                        Self::from_lean(object)
+                    }
+
+                    unsafe fn into_lean_unchecked(self) -> ::lean::lean_types::object::Object<#lifetime, Self> {
+                        unsafe {
+                            <::lean::lean_types::object::Object<#lifetime, Self> as ::lean::lean_types::Owner<_>>::new(
+                                ::lean_sys::lean_box(to_tag(&self) as usize),
+                            )
+                        }
+                    }
+                }
+
+                unsafe impl #impl_generics ::lean::lean_types::ctor::LeanField<#lifetime> for #name #ty_generics {
+                    const KIND: ::lean::lean_types::ctor::FieldKind = #field_kind;
+
+                    type Ref<#view_lifetime>
+                        = Self
+                    where
+                        #lifetime: #view_lifetime;
+
+                    unsafe fn read(ctor: ::lean_sys::b_lean_obj_arg, location: u32) -> Self {
+                        #field_read
+                    }
+
+                    unsafe fn read_ref<#view_lifetime>(ctor: ::lean_sys::b_lean_obj_arg, location: u32) -> Self
+                    where
+                        #lifetime: #view_lifetime,
+                    {
+                        unsafe { Self::read(ctor, location) }
+                    }
+
+                    unsafe fn write(self, ctor: ::lean_sys::b_lean_obj_arg, location: u32) {
+                        #field_write
+                    }
+                }
+            };
+        }
+    } else {
+        let view_name = parse_view_name(&derive_input.attrs, name)?;
+        let view_doc = LitStr::new(
+            &format!("A borrowed view of a Lean object of type [`{name}`]"),
+            name.span(),
+        );
+        let view_fields = ctors.iter().map(|ctor| ctor.view_fields(&view_lifetime));
+        let ctor_ident = format_ident!("ctor");
+        let reads = ctors.iter().zip(&idents).map(|(ctor, ident)| {
+            ctor.read(quote!(Self::#ident), &ctor_ident, &format_ident!("read"))
+        });
+        let view_reads = ctors.iter().zip(&idents).map(|(ctor, ident)| {
+            ctor.read(
+                quote!(#view_name::#ident),
+                &ctor_ident,
+                &format_ident!("read_ref"),
+            )
+        });
+        let patterns = ctors
+            .iter()
+            .zip(&idents)
+            .map(|(ctor, ident)| ctor.pattern(quote!(Self::#ident)));
+        let writes = ctors.iter().zip(&tags).map(|(ctor, &tag)| {
+            if ctor.len() == 0 {
+                quote!(::lean_sys::lean_box(#tag as usize))
+            } else {
+                ctor.write(tag)
+            }
+        });
+
+        quote! {
+            #[doc = #view_doc]
+            #[derive(Clone, Copy)]
+            #vis enum #view_name<#view_lifetime, #lifetime: #view_lifetime> {
+                #(#idents #view_fields,)*
+            }
+
+            const _: () = {
+                #(#layouts)*
+
+                unsafe impl #impl_generics ::lean::lean_types::ctor::LeanCtorTypeTag for #name #ty_generics {
+                    const LAYOUTS: &'static [::lean::lean_types::ctor::CtorLayout] = &[#(#layout_consts),*];
+                }
+
+                unsafe impl #impl_generics ::lean::lean_types::ctor::LeanInductive<#lifetime> for #name #ty_generics {
+                    type View<#view_lifetime>
+                        = #view_name<#view_lifetime, #lifetime>
+                    where
+                        #lifetime: #view_lifetime;
+
+                    fn from_lean(object: &::lean::lean_types::object::Obj<#lifetime, Self>) -> Self {
+                        unsafe {
+                            let #ctor_ident = ::lean::lean_types::Reference::as_mut_raw(object);
+                            match ::lean_sys::lean_obj_tag(#ctor_ident) {
+                                #(#tags => #reads,)*
+                                tag => panic!(#invalid_tag_message, tag),
+                            }
+                        }
+                    }
+
+                    fn view<#view_lifetime>(
+                        object: &#view_lifetime ::lean::lean_types::object::Obj<#lifetime, Self>,
+                    ) -> #view_name<#view_lifetime, #lifetime> {
+                        unsafe {
+                            let #ctor_ident = ::lean::lean_types::Reference::as_mut_raw(object);
+                            match ::lean_sys::lean_obj_tag(#ctor_ident) {
+                                #(#tags => #view_reads,)*
+                                tag => panic!(#invalid_tag_message, tag),
+                            }
+                        }
+                    }
+
+                    unsafe fn into_lean_unchecked(self) -> ::lean::lean_types::object::Object<#lifetime, Self> {
+                        unsafe {
+                            let ctor = match self {
+                                #(#patterns => { #writes })*
+                            };
+                            <::lean::lean_types::object::Object<#lifetime, Self> as ::lean::lean_types::Owner<_>>::new(ctor)
+                        }
+                    }
+                }
+
+                unsafe impl #impl_generics ::lean::lean_types::ctor::LeanField<#lifetime> for #name #ty_generics {
+                    const KIND: ::lean::lean_types::ctor::FieldKind =
+                        ::lean::lean_types::ctor::FieldKind::Object;
+
+                    type Ref<#view_lifetime>
+                        = &#view_lifetime ::lean::lean_types::object::Obj<#lifetime, Self>
+                    where
+                        #lifetime: #view_lifetime;
+
+                    unsafe fn read(ctor: ::lean_sys::b_lean_obj_arg, location: u32) -> Self {
+                        <Self as ::lean::lean_types::ctor::LeanInductive<#lifetime>>::from_lean(unsafe {
+                            Self::read_ref(ctor, location)
+                        })
+                    }
+
+                    unsafe fn read_ref<#view_lifetime>(
+                        ctor: ::lean_sys::b_lean_obj_arg,
+                        location: u32,
+                    ) -> &#view_lifetime ::lean::lean_types::object::Obj<#lifetime, Self>
+                    where
+                        #lifetime: #view_lifetime,
+                    {
+                        unsafe {
+                            <::lean::lean_types::object::Object<#lifetime, Self> as ::lean::lean_types::ctor::LeanField<#lifetime>>::read_ref(ctor, location)
+                        }
+                    }
+
+                    unsafe fn write(self, ctor: ::lean_sys::b_lean_obj_arg, location: u32) {
+                        unsafe {
+                            ::lean::lean_types::ctor::LeanField::<#lifetime>::write(
+                                <Self as ::lean::lean_types::ctor::LeanInductive<#lifetime>>::into_lean_unchecked(self),
+                                ctor,
+                                location,
+                            )
+                        }
+                    }
+                }
+            };
+        }
+    };
+    Ok(generated)
+}
@@ -0,0 +1,353 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    Attribute, Fields, GenericParam, Generics, Ident, Lifetime, LitInt, LitStr, Member, Type,
+    spanned::Spanned,
+    visit_mut::{self, VisitMut},
+};
+
+/// The lifetime of the runtime that the Lean objects of a derived type belong
+/// to, and the generics of the impls for the type
+pub struct Brand {
+    pub lifetime: Lifetime,
+    /// The generics of the impls, which declare the brand
+    pub impl_generics: TokenStream2,
+    /// The generics of the type, which are empty if the impls declare the
+    /// brand
+    pub ty_generics: TokenStream2,
+}
+
+impl Brand {
+    /// Uses the lifetime parameter of the type as the brand, or declares one
+    /// in the impls if the type has no lifetime parameter
+    ///
+    /// `message` is the error for types with other generic parameters.
+    pub fn new(generics: &Generics, message: &str) -> syn::Result<Self> {
+        let mut params = generics.params.iter();
+        match (params.next(), params.next()) {
+            (None, _) => {
+                let lifetime = Lifetime::new("'r", generics.span());
+                Ok(Self {
+                    impl_generics: quote!(<#lifetime>),
+                    ty_generics: quote!(),
+                    lifetime,
+                })
+            }
+            (Some(GenericParam::Lifetime(param)), None)
+                if param.bounds.is_empty() && generics.where_clause.is_none() =>
+            {
+                let lifetime = param.lifetime.clone();
+                Ok(Self {
+                    impl_generics: quote!(<#lifetime>),
+                    ty_generics: quote!(<#lifetime>),
+                    lifetime,
+                })
+            }
+            _ => Err(syn::Error::new(generics.span(), message)),
+        }
+    }
+
+    /// A lifetime for borrows of Lean objects that differs from the brand
+    pub fn borrow_lifetime(&self) -> Lifetime {
+        let name = if self.lifetime.ident == "a" {
+            "'b"
+        } else {
+            "'a"
+        };
+        Lifetime::new(name, self.lifetime.span())
+    }
+}
+
+/// Replaces the brand in field types with `'static`, since the layout
+/// constants cannot be generic
+struct ReplaceBrand<'a>(&'a Lifetime);
+
+impl VisitMut for ReplaceBrand<'_> {
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if lifetime.ident == self.0.ident {
+            *lifetime = Lifetime::new("'static", lifetime.span());
+        }
+        visit_mut::visit_lifetime_mut(self, lifetime);
+    }
+}
+
+/// Expected layout of a constructor, from `#[lean(...)]` attributes on a
+/// struct or an enum variant
+#[derive(Default)]
+struct ExpectedLayout {
+    num_objs: Option<u32>,
+    num_usizes: Option<u32>,
+    scalar_size: Option<u32>,
+}
+
+/// Expected position of a field, from a `#[lean(...)]` attribute on the field
+enum ExpectedPosition {
+    /// The index among the boxed or `USize` fields
+    Index(u32),
+    /// The offset in the scalar area
+    Offset(u32),
+}
+
+/// Parses the `#[lean(...)]` attributes of a struct or an enum variant,
+/// ignoring the keys in `other_keys`, which are handled by the caller
+fn parse_expected_layout(attrs: &[Attribute], other_keys: &[&str]) -> syn::Result<ExpectedLayout> {
+    let mut layout = ExpectedLayout::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("lean")) {
+        attr.parse_nested_meta(|meta| {
+            if other_keys.iter().any(|key| meta.path.is_ident(key)) {
+                let _: TokenStream2 = meta.value()?.parse()?;
+                return Ok(());
+            }
+            let value = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+            if meta.path.is_ident("num_objs") {
+                layout.num_objs = value;
+            } else if meta.path.is_ident("num_usizes") {
+                layout.num_usizes = value;
+            } else if meta.path.is_ident("scalar_size") {
+                layout.scalar_size = value;
+            } else {
+                return Err(meta.error(
+                    "expected `num_objs`, `num_usizes` or `scalar_size` in `#[lean(...)]`",
+                ));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(layout)
+}
+
+fn parse_expected_position(attrs: &[Attribute]) -> syn::Result<Option<ExpectedPosition>> {
+    let mut position = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("lean")) {
+        attr.parse_nested_meta(|meta| {
+            let value = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+            if meta.path.is_ident("index") {
+                position = Some(ExpectedPosition::Index(value));
+            } else if meta.path.is_ident("offset") {
+                position = Some(ExpectedPosition::Offset(value));
+            } else {
+                return Err(meta.error("expected `index` or `offset` in `#[lean(...)]`"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(position)
+}
+
+/// The relevant fields of a Lean constructor, as declared by a struct or an
+/// enum variant, and the names of the constants describing its layout
+pub struct Ctor<'a> {
+    fields: &'a Fields,
+    members: Vec<Member>,
+    bindings: Vec<Ident>,
+    types: Vec<&'a Type>,
+    brand: &'a Lifetime,
+    fields_const: Ident,
+    layout_const: Ident,
+    positions_const: Ident,
+    locations_const: Ident,
+}
+
+impl<'a> Ctor<'a> {
+    /// `suffix` distinguishes the layout constants of the constructors of
+    /// the same type
+    pub fn new(fields: &'a Fields, brand: &'a Lifetime, suffix: &str) -> Self {
+        let members = match fields {
+            Fields::Named(_) => fields
+                .iter()
+                .map(|field| Member::Named(field.ident.clone().unwrap()))
+                .collect(),
+            _ => (0..fields.len()).map(Member::from).collect(),
+        };
+        Self {
+            fields,
+            members,
+            bindings: (0..fields.len())
+                .map(|i| format_ident!("field_{i}"))
+                .collect(),
+            types: fields.iter().map(|field| &field.ty).collect(),
+            brand,
+            fields_const: format_ident!("FIELDS{suffix}"),
+            layout_const: format_ident!("LAYOUT{suffix}"),
+            positions_const: format_ident!("POSITIONS{suffix}"),
+            locations_const: format_ident!("LOCATIONS{suffix}"),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    pub fn layout_const(&self) -> &Ident {
+        &self.layout_const
+    }
+
+    /// Generates the layout constants of the constructor and the compile-time
+    /// assertions requested with `#[lean(...)]` attributes
+    ///
+    /// `description` names the constructor in assertion messages, and
+    /// `other_keys` lists the keys of `attrs` that are not layout assertions.
+    pub fn layout(
+        &self,
+        description: &str,
+        attrs: &[Attribute],
+        other_keys: &[&str],
+    ) -> syn::Result<TokenStream2> {
+        let Self {
+            types,
+            fields_const,
+            layout_const,
+            positions_const,
+            locations_const,
+            ..
+        } = self;
+        let indices: Vec<usize> = (0..self.len()).collect();
+        let num_fields = self.len();
+        let static_types = types.iter().map(|&ty| {
+            let mut ty = ty.clone();
+            ReplaceBrand(self.brand).visit_type_mut(&mut ty);
+            ty
+        });
+
+        let mut assertions = Vec::new();
+        let expected_layout = parse_expected_layout(attrs, other_keys)?;
+        for (count, value) in [
+            ("num_objs", expected_layout.num_objs),
+            ("num_usizes", expected_layout.num_usizes),
+            ("scalar_size", expected_layout.scalar_size),
+        ] {
+            if let Some(value) = value {
+                let count = Ident::new(count, self.fields.span());
+                let message = LitStr::new(
+                    &format!("the layout of {description} does not have `{count} = {value}`"),
+                    self.fields.span(),
+                );
+                assertions.push(quote! {
+                    assert!(#layout_const.#count == #value, #message);
+                });
+            }
+        }
+        for ((i, field), member) in self.fields.iter().enumerate().zip(&self.members) {
+            let member_name = quote!(#member).to_string();
+            let assertion = match parse_expected_position(&field.attrs)? {
+                None => continue,
+                Some(ExpectedPosition::Index(index)) => {
+                    let message = LitStr::new(
+                        &format!(
+                            "field `{member_name}` of {description} is not boxed or `USize` field {index}"
+                        ),
+                        field.span(),
+                    );
+                    quote! {
+                        assert!(
+                            matches!(#fields_const[#i], ::lean::lean_types::ctor::FieldKind::Object | ::lean::lean_types::ctor::FieldKind::Usize)
+                                && #positions_const[#i] == #index,
+                            #message
+                        );
+                    }
+                }
+                Some(ExpectedPosition::Offset(offset)) => {
+                    let message = LitStr::new(
+                        &format!(
+                            "field `{member_name}` of {description} is not a scalar field at offset {offset}"
+                        ),
+                        field.span(),
+                    );
+                    quote! {
+                        assert!(
+                            matches!(#fields_const[#i], ::lean::lean_types::ctor::FieldKind::Scalar(_))
+                                && #positions_const[#i] == #offset,
+                            #message
+                        );
+                    }
+                }
+            };
+            assertions.push(assertion);
+        }
+
+        Ok(quote! {
+            const #fields_const: &[::lean::lean_types::ctor::FieldKind] = &[
+                #(<#static_types as ::lean::lean_types::ctor::LeanField<'static>>::KIND,)*
+            ];
+            const #layout_const: ::lean::lean_types::ctor::CtorLayout =
+                ::lean::lean_types::ctor::CtorLayout::from_fields(#fields_const);
+            const #positions_const: [u32; #num_fields] = [
+                #(::lean::lean_types::ctor::CtorLayout::field_position(#fields_const, #indices),)*
+            ];
+            const #locations_const: [u32; #num_fields] = [
+                #(#layout_const.field_location(#fields_const[#indices], #positions_const[#indices]),)*
+            ];
+
+            #(#assertions)*
+        })
+    }
+
+    /// Generates an expression that constructs `path` from the fields of
+    /// `ctor`, using `method` of `LeanField` to read each field
+    pub fn read(&self, path: TokenStream2, ctor: &Ident, method: &Ident) -> TokenStream2 {
+        let Self {
+            members,
+            types,
+            brand,
+            locations_const,
+            ..
+        } = self;
+        let indices = 0..self.len();
+        quote! {
+            #path {
+                #(#members: <#types as ::lean::lean_types::ctor::LeanField<#brand>>::#method(#ctor, #locations_const[#indices]),)*
+            }
+        }
+    }
+
+    /// Generates a pattern that binds the fields of `path`
+    pub fn pattern(&self, path: TokenStream2) -> TokenStream2 {
+        let Self {
+            members, bindings, ..
+        } = self;
+        quote!(#path { #(#members: #bindings),* })
+    }
+
+    /// Generates statements that allocate a constructor object with the given
+    /// tag and move the fields bound by [`Ctor::pattern()`] into it, which
+    /// evaluate to the raw object
+    pub fn write(&self, tag: u32) -> TokenStream2 {
+        let Self {
+            bindings,
+            types,
+            brand,
+            layout_const,
+            locations_const,
+            ..
+        } = self;
+        let indices = 0..self.len();
+        quote! {
+            let ctor = ::lean_sys::lean_alloc_ctor(
+                #tag,
+                #layout_const.num_objs,
+                #layout_const.allocation_scalar_size(),
+            );
+            #(<#types as ::lean::lean_types::ctor::LeanField<#brand>>::write(#bindings, ctor, #locations_const[#indices]);)*
+            ctor
+        }
+    }
+
+    /// Generates the fields of a variant of a borrowed view enum, which borrow
+    /// for `lifetime`
+    pub fn view_fields(&self, lifetime: &Lifetime) -> TokenStream2 {
+        let brand = self.brand;
+        let fields = self.fields.iter().map(|field| {
+            let ty = &field.ty;
+            let ty = quote!(<#ty as ::lean::lean_types::ctor::LeanField<#brand>>::Ref<#lifetime>);
+            match &field.ident {
+                Some(ident) => quote!(#ident: #ty),
+                None => ty,
+            }
+        });
+        match self.fields {
+            Fields::Named(_) => quote!({ #(#fields),* }),
+            Fields::Unnamed(_) => quote!(( #(#fields),* )),
+            Fields::Unit => quote!(),
+        }
+    }
+}
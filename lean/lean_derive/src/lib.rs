@@ -1,4 +1,7 @@
+mod inductive;
+mod layout;
 mod module;
+mod structure;
 
 #[proc_macro_derive(Modules)]
 pub fn modules_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -6,3 +9,35 @@ pub fn modules_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 
     output.unwrap_or_else(syn::Error::into_compile_error).into()
 }
+
+/// Derives `LeanStructure` for a struct whose fields mirror the relevant fields
+/// of a Lean structure, in declaration order
+///
+/// The layout is computed from the field types following the Lean compiler's
+/// rules. Optional `#[lean(num_objs = _, num_usizes = _, scalar_size = _)]`
+/// attributes on the struct, and `#[lean(index = _)]` or `#[lean(offset = _)]`
+/// attributes on fields, are checked against the computed layout at compile
+/// time. Structs with boxed fields take the lifetime of the runtime as their
+/// only generic parameter.
+#[proc_macro_derive(LeanStructure, attributes(lean))]
+pub fn lean_structure_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let output = structure::impl_lean_structure(input.into());
+
+    output.unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Derives `LeanInductive` for an enum whose variants mirror the constructors
+/// of a Lean inductive type, in declaration order
+///
+/// For enums with data-carrying variants, a borrowed view enum named after the
+/// enum with a `View` suffix is also generated, which can be renamed with
+/// `#[lean(view = Name)]`. The view enum borrows for its first lifetime
+/// parameter, and its second one is the lifetime of the runtime. Enums accept
+/// the same generic parameters, and variants the same layout attributes, as
+/// `#[derive(LeanStructure)]`.
+#[proc_macro_derive(LeanInductive, attributes(lean))]
+pub fn lean_inductive_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let output = inductive::impl_lean_inductive(input.into());
+
+    output.unwrap_or_else(syn::Error::into_compile_error).into()
+}
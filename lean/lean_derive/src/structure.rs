@@ -0,0 +1,75 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, spanned::Spanned};
+
+use crate::layout::{Brand, Ctor};
+
+pub fn impl_lean_structure(input: TokenStream2) -> syn::Result<TokenStream2> {
+    let derive_input: DeriveInput = syn::parse2(input)?;
+    let name = &derive_input.ident;
+
+    let brand = Brand::new(
+        &derive_input.generics,
+        "`LeanStructure` can only be derived for structs whose only generic parameter is the \
+         lifetime of the runtime",
+    )?;
+    let fields = match &derive_input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new(
+                name.span(),
+                "`LeanStructure` can only be derived for structs",
+            ));
+        }
+    };
+    // Lean represents structures with no relevant fields as scalars and
+    // structures with a single relevant field as that field
+    if fields.len() < 2 {
+        return Err(syn::Error::new(
+            fields.span(),
+            "Lean does not represent structures with fewer than two relevant fields as \
+             constructor objects, so `LeanStructure` requires at least two fields",
+        ));
+    }
+
+    let ctor = Ctor::new(fields, &brand.lifetime, "");
+    let layout = ctor.layout(&format!("`{name}`"), &derive_input.attrs, &[])?;
+    let layout_const = ctor.layout_const();
+    let ctor_ident = format_ident!("ctor");
+    let read = ctor.read(quote!(Self), &ctor_ident, &format_ident!("read"));
+    let pattern = ctor.pattern(quote!(Self));
+    let write = ctor.write(0);
+    let Brand {
+        lifetime,
+        impl_generics,
+        ty_generics,
+    } = &brand;
+
+    let generated = quote! {
+        const _: () = {
+            #layout
+
+            unsafe impl #impl_generics ::lean::lean_types::ctor::LeanCtorTypeTag for #name #ty_generics {
+                const LAYOUTS: &'static [::lean::lean_types::ctor::CtorLayout] = &[#layout_const];
+            }
+
+            unsafe impl #impl_generics ::lean::lean_types::ctor::LeanStructure<#lifetime> for #name #ty_generics {
+                fn from_lean(object: &::lean::lean_types::object::Obj<#lifetime, Self>) -> Self {
+                    unsafe {
+                        let #ctor_ident = ::lean::lean_types::Reference::as_mut_raw(object);
+                        #read
+                    }
+                }
+
+                unsafe fn into_lean_unchecked(self) -> ::lean::lean_types::object::Object<#lifetime, Self> {
+                    let #pattern = self;
+                    unsafe {
+                        let ctor = { #write };
+                        <::lean::lean_types::object::Object<#lifetime, Self> as ::lean::lean_types::Owner<_>>::new(ctor)
+                    }
+                }
+            }
+        };
+    };
+    Ok(generated)
+}
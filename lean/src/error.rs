@@ -1,37 +1,248 @@
 use std::error::Error;
-use std::ffi::CString;
 use std::fmt;
+use std::io;
 
-use lean_sys::{b_lean_obj_arg, lean_inc, lean_io_error_to_string, lean_io_result_get_error};
+use lean_sys::{b_lean_obj_arg, lean_io_result_get_error};
 
-use crate::lean_types::{Owner, string::LeanString};
+use crate::lean_types::{
+    Borrower,
+    io_error::IoErrorTypeTag,
+    object::{Obj, Object},
+};
 
-#[derive(Debug, Eq, PartialEq)]
-pub struct LeanIoError(pub CString);
+/// A constructor of Lean's `IO.Error`, with its fields
+///
+/// `os_code` is the `errno` value that caused the error, or 0 if the error
+/// does not come from the operating system.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LeanIoErrorKind {
+    AlreadyExists {
+        filename: Option<String>,
+        os_code: u32,
+        details: String,
+    },
+    OtherError {
+        os_code: u32,
+        details: String,
+    },
+    ResourceBusy {
+        os_code: u32,
+        details: String,
+    },
+    ResourceVanished {
+        os_code: u32,
+        details: String,
+    },
+    UnsupportedOperation {
+        os_code: u32,
+        details: String,
+    },
+    HardwareFault {
+        os_code: u32,
+        details: String,
+    },
+    UnsatisfiedConstraints {
+        os_code: u32,
+        details: String,
+    },
+    IllegalOperation {
+        os_code: u32,
+        details: String,
+    },
+    ProtocolError {
+        os_code: u32,
+        details: String,
+    },
+    TimeExpired {
+        os_code: u32,
+        details: String,
+    },
+    Interrupted {
+        filename: String,
+        os_code: u32,
+        details: String,
+    },
+    NoFileOrDirectory {
+        filename: String,
+        os_code: u32,
+        details: String,
+    },
+    InvalidArgument {
+        filename: Option<String>,
+        os_code: u32,
+        details: String,
+    },
+    PermissionDenied {
+        filename: Option<String>,
+        os_code: u32,
+        details: String,
+    },
+    ResourceExhausted {
+        filename: Option<String>,
+        os_code: u32,
+        details: String,
+    },
+    InappropriateType {
+        filename: Option<String>,
+        os_code: u32,
+        details: String,
+    },
+    NoSuchThing {
+        filename: Option<String>,
+        os_code: u32,
+        details: String,
+    },
+    UnexpectedEof,
+    UserError {
+        message: String,
+    },
+}
+
+impl LeanIoErrorKind {
+    /// The file that the failed operation accessed, if any
+    pub fn filename(&self) -> Option<&str> {
+        match self {
+            Self::Interrupted { filename, .. } | Self::NoFileOrDirectory { filename, .. } => {
+                Some(filename)
+            }
+            Self::AlreadyExists { filename, .. }
+            | Self::InvalidArgument { filename, .. }
+            | Self::PermissionDenied { filename, .. }
+            | Self::ResourceExhausted { filename, .. }
+            | Self::InappropriateType { filename, .. }
+            | Self::NoSuchThing { filename, .. } => filename.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The `errno` value that caused the error, if any
+    pub fn os_code(&self) -> Option<u32> {
+        match self {
+            Self::AlreadyExists { os_code, .. }
+            | Self::OtherError { os_code, .. }
+            | Self::ResourceBusy { os_code, .. }
+            | Self::ResourceVanished { os_code, .. }
+            | Self::UnsupportedOperation { os_code, .. }
+            | Self::HardwareFault { os_code, .. }
+            | Self::UnsatisfiedConstraints { os_code, .. }
+            | Self::IllegalOperation { os_code, .. }
+            | Self::ProtocolError { os_code, .. }
+            | Self::TimeExpired { os_code, .. }
+            | Self::Interrupted { os_code, .. }
+            | Self::NoFileOrDirectory { os_code, .. }
+            | Self::InvalidArgument { os_code, .. }
+            | Self::PermissionDenied { os_code, .. }
+            | Self::ResourceExhausted { os_code, .. }
+            | Self::InappropriateType { os_code, .. }
+            | Self::NoSuchThing { os_code, .. } => Some(*os_code).filter(|&code| code != 0),
+            Self::UnexpectedEof | Self::UserError { .. } => None,
+        }
+    }
+
+    /// The description of the error, usually from `strerror()`, or the
+    /// message of a user error
+    pub fn details(&self) -> Option<&str> {
+        match self {
+            Self::AlreadyExists { details, .. }
+            | Self::OtherError { details, .. }
+            | Self::ResourceBusy { details, .. }
+            | Self::ResourceVanished { details, .. }
+            | Self::UnsupportedOperation { details, .. }
+            | Self::HardwareFault { details, .. }
+            | Self::UnsatisfiedConstraints { details, .. }
+            | Self::IllegalOperation { details, .. }
+            | Self::ProtocolError { details, .. }
+            | Self::TimeExpired { details, .. }
+            | Self::Interrupted { details, .. }
+            | Self::NoFileOrDirectory { details, .. }
+            | Self::InvalidArgument { details, .. }
+            | Self::PermissionDenied { details, .. }
+            | Self::ResourceExhausted { details, .. }
+            | Self::InappropriateType { details, .. }
+            | Self::NoSuchThing { details, .. }
+            | Self::UserError { message: details } => Some(details),
+            Self::UnexpectedEof => None,
+        }
+    }
+
+    /// The closest [`io::ErrorKind`]
+    ///
+    /// Errors that come from the operating system use the standard library's
+    /// mapping of their `errno` value.
+    pub fn io_error_kind(&self) -> io::ErrorKind {
+        if let Some(os_code) = self.os_code() {
+            return io::Error::from_raw_os_error(os_code as i32).kind();
+        }
+        match self {
+            Self::AlreadyExists { .. } => io::ErrorKind::AlreadyExists,
+            Self::ResourceBusy { .. } => io::ErrorKind::ResourceBusy,
+            Self::ResourceVanished { .. } => io::ErrorKind::BrokenPipe,
+            Self::UnsupportedOperation { .. } => io::ErrorKind::Unsupported,
+            Self::ProtocolError { .. } => io::ErrorKind::InvalidData,
+            Self::TimeExpired { .. } => io::ErrorKind::TimedOut,
+            Self::Interrupted { .. } => io::ErrorKind::Interrupted,
+            Self::NoFileOrDirectory { .. } | Self::NoSuchThing { .. } => io::ErrorKind::NotFound,
+            Self::InvalidArgument { .. } => io::ErrorKind::InvalidInput,
+            Self::PermissionDenied { .. } => io::ErrorKind::PermissionDenied,
+            Self::UnexpectedEof => io::ErrorKind::UnexpectedEof,
+            Self::OtherError { .. }
+            | Self::HardwareFault { .. }
+            | Self::UnsatisfiedConstraints { .. }
+            | Self::IllegalOperation { .. }
+            | Self::ResourceExhausted { .. }
+            | Self::InappropriateType { .. }
+            | Self::UserError { .. } => io::ErrorKind::Other,
+        }
+    }
+}
+
+/// A decoded Lean `IO.Error`
+///
+/// Unlike an `IO.Error` object, which can be re-raised in Lean, this type does
+/// not depend on the Lean runtime, so it can outlive the runtime and be sent to
+/// other threads.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LeanIoError {
+    kind: LeanIoErrorKind,
+    message: String,
+}
 
 impl fmt::Display for LeanIoError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(&self.0.to_string_lossy())
+        f.write_str(&self.message)
     }
 }
 
 impl Error for LeanIoError {}
 
 impl LeanIoError {
+    pub(crate) fn new(kind: LeanIoErrorKind, message: String) -> Self {
+        Self { kind, message }
+    }
+
+    /// The constructor of the `IO.Error` and its fields
+    pub fn kind(&self) -> &LeanIoErrorKind {
+        &self.kind
+    }
+
+    /// The closest [`io::ErrorKind`], see [`LeanIoErrorKind::io_error_kind()`]
+    pub fn io_error_kind(&self) -> io::ErrorKind {
+        self.kind.io_error_kind()
+    }
+
+    /// The message that Lean's `IO.Error.toString` produces
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
     /// Create an instance from a Lean IO error
     ///
     /// # Safety
     ///
     /// Callers must ensure that `lean_io_error` points to a valid error object
     pub unsafe fn from_lean_io_error(lean_io_error: b_lean_obj_arg) -> Self {
-        let lean_cstring;
-        unsafe {
-            lean_inc(lean_io_error);
-            let lean_string = lean_io_error_to_string(lean_io_error);
-            lean_cstring = LeanString::new(lean_string);
-        }
-        let owned_string = lean_cstring.as_cstr().to_owned();
-        Self(owned_string)
+        let lean_io_error: &Obj<'_, IoErrorTypeTag> = unsafe { Obj::new(&lean_io_error) };
+        lean_io_error.to_error()
     }
 
     /// Create an instance from a Lean IO error contained in a Lean IO result
@@ -48,6 +259,18 @@ impl LeanIoError {
     }
 }
 
+impl From<Object<'_, IoErrorTypeTag>> for LeanIoError {
+    fn from(lean_io_error: Object<'_, IoErrorTypeTag>) -> Self {
+        lean_io_error.to_error()
+    }
+}
+
+impl From<LeanIoError> for io::Error {
+    fn from(error: LeanIoError) -> Self {
+        io::Error::new(error.io_error_kind(), error)
+    }
+}
+
 #[derive(thiserror::Error, Debug, Eq, PartialEq)]
 pub enum LeanError<
     RuntimeInitializationError: Error,
@@ -0,0 +1,57 @@
+use lean_sys::{
+    lean_dec, lean_inc, lean_io_mk_world, lean_io_result_get_error, lean_io_result_get_value,
+    lean_io_result_is_ok, lean_obj_arg, lean_obj_res,
+};
+
+use crate::lean_types::LeanValue;
+
+/// An owned result of an `IO` or `EIO` action
+pub(crate) struct IoResult(lean_obj_res);
+
+impl IoResult {
+    /// Runs an action with a new world token
+    ///
+    /// # Safety
+    ///
+    /// The Lean runtime must be initialized and `io_action` must return an
+    /// owned `EIO` result.
+    pub(crate) unsafe fn run<F: FnOnce(lean_obj_arg) -> lean_obj_res>(io_action: F) -> Self {
+        Self(io_action(unsafe { lean_io_mk_world() }))
+    }
+
+    pub(crate) fn is_ok(&self) -> bool {
+        unsafe { lean_io_result_is_ok(self.0) }
+    }
+
+    /// Transfers the result's reference counting token to the caller
+    pub(crate) fn into_raw(self) -> lean_obj_res {
+        let result = self.0;
+        std::mem::forget(self);
+        result
+    }
+
+    /// Extracts the value or the error of the result
+    ///
+    /// # Safety
+    ///
+    /// The value and error must be of types `T` and `E`, respectively.
+    pub(crate) unsafe fn into_result<T: LeanValue, E: LeanValue>(self) -> Result<T, E> {
+        unsafe {
+            if self.is_ok() {
+                let value = lean_io_result_get_value(self.0);
+                lean_inc(value);
+                Ok(T::from_boxed(value))
+            } else {
+                let error = lean_io_result_get_error(self.0);
+                lean_inc(error);
+                Err(E::from_boxed(error))
+            }
+        }
+    }
+}
+
+impl Drop for IoResult {
+    fn drop(&mut self) {
+        unsafe { lean_dec(self.0) };
+    }
+}
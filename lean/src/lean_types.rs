@@ -1,12 +1,35 @@
 use std::borrow::Borrow;
 
-use lean_sys::{b_lean_obj_arg, lean_obj_arg, lean_object};
+use lean_sys::{
+    b_lean_obj_arg, lean_alloc_ctor, lean_box, lean_box_float, lean_box_float32, lean_box_uint32,
+    lean_box_uint64, lean_box_usize, lean_ctor_get, lean_ctor_set, lean_dec, lean_inc,
+    lean_is_scalar, lean_obj_arg, lean_obj_res, lean_object, lean_unbox, lean_unbox_float,
+    lean_unbox_float32, lean_unbox_uint32, lean_unbox_uint64, lean_unbox_usize,
+};
 
+pub mod any;
 pub mod array;
 pub mod byte_array;
+pub mod closure;
+pub mod ctor;
+pub mod except;
+pub mod external;
 pub mod float_array;
+pub mod int;
+pub mod io_error;
+pub mod nat;
 pub mod object;
+pub mod option;
+pub mod prod;
+pub mod promise;
 pub mod string;
+pub mod sum;
+pub mod task;
+
+use any::LeanTypeTag;
+use object::Object;
+
+use crate::{Minimal, Modules, Runtime};
 
 /// A trait implemented by types that point to immutable Lean objects
 ///
@@ -37,13 +60,20 @@ pub unsafe trait Reference {
 /// Implementations of this trait must guarantee that the Lean objects are never
 /// mutated when used exclusively through their methods.
 pub unsafe trait Borrower: Reference + ToOwned {
-    /// Create an instance that wraps an existing object
+    /// Views a borrowed object, such as a borrowed argument of an FFI
+    /// callback
+    ///
+    /// The returned reference cannot outlive `obj`, so it cannot escape the
+    /// scope of the callback argument it was created from.
     ///
     /// # Safety
     ///
-    /// Callers must ensure that `obj` points to the same borrowed object for the
-    /// lifetime of the new instance and that the object is of the correct type.
-    unsafe fn new(obj: b_lean_obj_arg) -> Self;
+    /// Callers must ensure that `obj` points to an object of the correct type
+    /// that stays borrowed for as long as `obj` itself is borrowed, and that
+    /// belongs to a runtime that outlives the brand of the returned reference.
+    /// With the `debug-checks` feature, objects whose kind does not match the
+    /// type tag cause a panic.
+    unsafe fn new(obj: &b_lean_obj_arg) -> &Self;
 }
 
 /// A trait implemented by types that own immutable Lean objects
@@ -68,7 +98,10 @@ pub unsafe trait Owner<T: Borrower>: Reference + Borrow<T> {
     ///
     /// Callers must ensure that `obj`` has an associated reference counting
     /// token, points to the same object for the lifetime of the new instance, and
-    /// that the object is of the correct type.
+    /// that the object is of the correct type. The object must belong to a
+    /// runtime that outlives the lifetime that brands the new instance. With
+    /// the `debug-checks` feature, objects whose kind does not match the type
+    /// tag cause a panic.
     unsafe fn new(obj: lean_obj_arg) -> Self;
 
     /// Transfers this object's reference counting token to the caller
@@ -77,3 +110,151 @@ pub unsafe trait Owner<T: Borrower>: Reference + Borrow<T> {
     /// Create a new owning reference to the same Lean object
     fn share(&self) -> Self;
 }
+
+/// A Lean collection that can be built from an iterator of unknown length
+///
+/// This is the Lean counterpart of [`FromIterator`], which needs a runtime to
+/// allocate the collection.
+pub trait LeanFromIterator<'r, T>: Sized {
+    fn from_lean_iter<R: Minimal, M: Modules, I: IntoIterator<Item = T>>(
+        runtime: &'r Runtime<R, M>,
+        iter: I,
+    ) -> Self;
+}
+
+/// An extension trait to collect iterators into Lean collections
+pub trait CollectIntoLean: Iterator + Sized {
+    /// Collects the iterator into a Lean collection without an intermediate
+    /// `Vec`
+    fn collect_into_lean<'r, C: LeanFromIterator<'r, Self::Item>, R: Minimal, M: Modules>(
+        self,
+        runtime: &'r Runtime<R, M>,
+    ) -> C {
+        C::from_lean_iter(runtime, self)
+    }
+}
+
+impl<I: Iterator> CollectIntoLean for I {}
+
+/// A Rust value that Lean passes around in boxed form
+///
+/// Lean closures take and return all values in boxed form, and the results of
+/// IO actions also contain boxed values: objects are passed as is, whereas
+/// scalars are boxed.
+///
+/// # Safety
+///
+/// Implementations must box and unbox values as the Lean compiler does for
+/// the corresponding Lean type.
+pub unsafe trait LeanValue: Sized {
+    /// Create an owned Lean object (or a scalar) that represents the value
+    fn into_boxed(self) -> lean_obj_res;
+
+    /// Take ownership of a boxed Lean object (or a scalar)
+    ///
+    /// # Safety
+    ///
+    /// `boxed` must be an owned object of the expected type, which belongs to a
+    /// runtime that outlives the brand of `Self`, if any.
+    unsafe fn from_boxed(boxed: lean_obj_arg) -> Self;
+}
+
+unsafe impl<TypeTag: LeanTypeTag> LeanValue for Object<'_, TypeTag> {
+    fn into_boxed(self) -> lean_obj_res {
+        self.into_raw()
+    }
+
+    unsafe fn from_boxed(boxed: lean_obj_arg) -> Self {
+        unsafe { Self::new(boxed) }
+    }
+}
+
+/// `Unit` and other types with a single value are boxed as `lean_box(0)`
+unsafe impl LeanValue for () {
+    fn into_boxed(self) -> lean_obj_res {
+        unsafe { lean_box(0) }
+    }
+
+    unsafe fn from_boxed(_boxed: lean_obj_arg) -> Self {}
+}
+
+/// `Option α` is boxed as for [`option::OptionTypeTag`], with `α` in boxed form
+unsafe impl<T: LeanValue> LeanValue for Option<T> {
+    fn into_boxed(self) -> lean_obj_res {
+        match self {
+            None => unsafe { lean_box(0) },
+            Some(value) => unsafe {
+                let option = lean_alloc_ctor(1, 1, 0);
+                lean_ctor_set(option, 0, value.into_boxed());
+                option
+            },
+        }
+    }
+
+    unsafe fn from_boxed(boxed: lean_obj_arg) -> Self {
+        unsafe {
+            if lean_is_scalar(boxed) {
+                None
+            } else {
+                let value = lean_ctor_get(boxed, 0);
+                lean_inc(value);
+                lean_dec(boxed);
+                Some(T::from_boxed(value))
+            }
+        }
+    }
+}
+
+/// Implements `LeanValue` for a scalar type that is boxed as the unsigned
+/// integer or floating-point type `$bits_type`
+macro_rules! impl_lean_value_for_scalar {
+    ($type:ty, $bits_type:ty, $lean_box:ident, $lean_unbox:ident) => {
+        unsafe impl LeanValue for $type {
+            fn into_boxed(self) -> lean_obj_res {
+                unsafe { $lean_box(self as $bits_type as _) }
+            }
+
+            unsafe fn from_boxed(boxed: lean_obj_arg) -> Self {
+                unsafe {
+                    let value = $lean_unbox(boxed);
+                    lean_dec(boxed);
+                    value as $bits_type as $type
+                }
+            }
+        }
+    };
+}
+
+impl_lean_value_for_scalar!(u8, u8, lean_box, lean_unbox);
+impl_lean_value_for_scalar!(i8, u8, lean_box, lean_unbox);
+impl_lean_value_for_scalar!(u16, u16, lean_box, lean_unbox);
+impl_lean_value_for_scalar!(i16, u16, lean_box, lean_unbox);
+impl_lean_value_for_scalar!(u32, u32, lean_box_uint32, lean_unbox_uint32);
+impl_lean_value_for_scalar!(i32, u32, lean_box_uint32, lean_unbox_uint32);
+impl_lean_value_for_scalar!(u64, u64, lean_box_uint64, lean_unbox_uint64);
+impl_lean_value_for_scalar!(i64, u64, lean_box_uint64, lean_unbox_uint64);
+impl_lean_value_for_scalar!(usize, usize, lean_box_usize, lean_unbox_usize);
+impl_lean_value_for_scalar!(isize, usize, lean_box_usize, lean_unbox_usize);
+impl_lean_value_for_scalar!(f32, f32, lean_box_float32, lean_unbox_float32);
+impl_lean_value_for_scalar!(f64, f64, lean_box_float, lean_unbox_float);
+
+unsafe impl LeanValue for bool {
+    fn into_boxed(self) -> lean_obj_res {
+        unsafe { lean_box(self.into()) }
+    }
+
+    unsafe fn from_boxed(boxed: lean_obj_arg) -> Self {
+        unsafe { lean_unbox(boxed) != 0 }
+    }
+}
+
+unsafe impl LeanValue for char {
+    fn into_boxed(self) -> lean_obj_res {
+        unsafe { lean_box_uint32(self.into()) }
+    }
+
+    unsafe fn from_boxed(boxed: lean_obj_arg) -> Self {
+        // Lean's `Char` is guaranteed to be a valid Unicode scalar value
+        unsafe { char::from_u32_unchecked(lean_unbox_uint32(boxed)) }
+    }
+}
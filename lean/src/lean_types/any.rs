@@ -0,0 +1,216 @@
+use std::borrow::Borrow;
+
+use lean_sys::{
+    LeanArray, LeanClosure, LeanExternal, LeanMPZ, LeanMaxCtorTag, LeanPromise, LeanRef,
+    LeanScalarArray, LeanString, LeanStructArray, LeanTask, LeanThunk, lean_ctor_get,
+    lean_ctor_num_objs, lean_is_scalar, lean_ptr_tag, lean_sarray_elem_size, lean_unbox,
+};
+
+use super::{
+    Owner, Reference,
+    object::{Obj, Object},
+};
+
+/// The type tag of Lean objects of unknown type
+///
+/// Dynamically typed objects, such as the results of evaluated expressions or
+/// the values of `Dynamic`, can be inspected with [`Obj::object_kind()`] and
+/// converted to typed objects with checked downcasts.
+pub enum AnyTypeTag {}
+
+pub type AnyObj<'r> = Obj<'r, AnyTypeTag>;
+pub type AnyObject<'r> = Object<'r, AnyTypeTag>;
+
+/// The runtime representation of a Lean object, as given by its header
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ObjectKind {
+    /// A boxed scalar, such as a small `Nat` or a constructor without relevant
+    /// fields, with its unboxed value
+    Scalar(usize),
+    /// A constructor object with the given constructor tag and number of boxed
+    /// fields
+    Ctor {
+        tag: u32,
+        num_objs: u32,
+    },
+    Promise,
+    Closure,
+    /// An array of boxed values, such as `Array α`
+    Array,
+    StructArray,
+    /// An array of unboxed scalars, such as `ByteArray`, with the size of its
+    /// elements in bytes
+    ScalarArray {
+        elem_size: u32,
+    },
+    String,
+    /// A natural number or integer that does not fit in a scalar
+    Mpz,
+    Thunk,
+    Task,
+    Ref,
+    External,
+    Reserved,
+}
+
+/// A type tag that declares the runtime representation of its objects
+///
+/// With the `debug-checks` feature, [`Owner::new()`] and
+/// [`Borrower::new()`](super::Borrower::new) panic when given an object whose
+/// kind is not accepted by its type tag, instead of silently misinterpreting
+/// it.
+pub trait LeanTypeTag {
+    /// Whether objects of this type can be represented by objects of `kind`
+    fn accepts_kind(kind: ObjectKind) -> bool;
+}
+
+impl LeanTypeTag for AnyTypeTag {
+    fn accepts_kind(_kind: ObjectKind) -> bool {
+        true
+    }
+}
+
+/// Panics if `object` cannot be an object of the type of `TypeTag`
+#[cfg(feature = "debug-checks")]
+pub(crate) fn check_object_kind<TypeTag: LeanTypeTag>(object: &AnyObj<'_>) {
+    let kind = object.object_kind();
+    assert!(
+        TypeTag::accepts_kind(kind),
+        "object of kind {kind:?} cannot be wrapped as an object of type tag `{}`",
+        std::any::type_name::<TypeTag>()
+    );
+}
+
+/// A type tag whose objects can be recognized at runtime
+///
+/// # Safety
+///
+/// Implementations must only accept objects that can be used through the
+/// wrappers of this type tag, including the elements and fields that the
+/// wrappers access. Type tags must not be branded with the lifetime of a
+/// runtime, which cannot be checked: their wrappers brand the objects they
+/// return with the lifetime of the checked object instead.
+pub unsafe trait CheckedTypeTag: LeanTypeTag {
+    /// Whether `object` is an object of this type
+    fn matches(object: &AnyObj<'_>) -> bool;
+}
+
+unsafe impl CheckedTypeTag for AnyTypeTag {
+    fn matches(_object: &AnyObj<'_>) -> bool {
+        true
+    }
+}
+
+impl<'r, TypeTag> Obj<'r, TypeTag> {
+    /// Inspects the header of this object
+    pub fn object_kind(&self) -> ObjectKind {
+        unsafe {
+            let object = self.as_mut_raw();
+            if lean_is_scalar(object) {
+                return ObjectKind::Scalar(lean_unbox(object));
+            }
+            // The object tags are named as in `lean.h`
+            #[allow(non_upper_case_globals)]
+            match u32::from(lean_ptr_tag(object)) {
+                tag @ 0..=LeanMaxCtorTag => ObjectKind::Ctor {
+                    tag,
+                    num_objs: lean_ctor_num_objs(object),
+                },
+                LeanPromise => ObjectKind::Promise,
+                LeanClosure => ObjectKind::Closure,
+                LeanArray => ObjectKind::Array,
+                LeanStructArray => ObjectKind::StructArray,
+                LeanScalarArray => ObjectKind::ScalarArray {
+                    elem_size: lean_sarray_elem_size(object),
+                },
+                LeanString => ObjectKind::String,
+                LeanMPZ => ObjectKind::Mpz,
+                LeanThunk => ObjectKind::Thunk,
+                LeanTask => ObjectKind::Task,
+                LeanRef => ObjectKind::Ref,
+                LeanExternal => ObjectKind::External,
+                _ => ObjectKind::Reserved,
+            }
+        }
+    }
+
+    /// Forgets the type of this object
+    pub fn as_any(&self) -> &AnyObj<'r> {
+        unsafe { Obj::from_raw(self.as_mut_raw()) }
+    }
+}
+
+impl<'r, TypeTag> Object<'r, TypeTag> {
+    pub fn object_kind(&self) -> ObjectKind {
+        <Self as Borrow<Obj<_>>>::borrow(self).object_kind()
+    }
+}
+
+impl<'r, TypeTag: LeanTypeTag> Object<'r, TypeTag> {
+    pub fn into_any(self) -> AnyObject<'r> {
+        unsafe { Object::new(self.into_raw()) }
+    }
+}
+
+impl<'r> Obj<'r, AnyTypeTag> {
+    /// Whether this object is of the type of `TypeTag`
+    ///
+    /// Types that share a runtime representation cannot be told apart, such as
+    /// arrays of `UInt64`, `Int64`, `USize` and `Float`, whose elements are
+    /// boxed scalars of the same size.
+    pub fn is<TypeTag: CheckedTypeTag>(&self) -> bool {
+        TypeTag::matches(self)
+    }
+
+    /// Views this object as an object of the type of `TypeTag`, if it is one
+    pub fn downcast_ref<TypeTag: CheckedTypeTag>(&self) -> Option<&Obj<'r, TypeTag>> {
+        if self.is::<TypeTag>() {
+            Some(unsafe { Obj::from_raw(self.as_mut_raw()) })
+        } else {
+            None
+        }
+    }
+
+    /// Views a boxed field of this constructor object
+    ///
+    /// # Safety
+    ///
+    /// This object must be a constructor object with more than `index` boxed
+    /// fields.
+    pub(crate) unsafe fn ctor_field(&self, index: u32) -> &AnyObj<'r> {
+        unsafe { Obj::from_raw(lean_ctor_get(self.as_mut_raw(), index)) }
+    }
+}
+
+impl<'r> Object<'r, AnyTypeTag> {
+    pub fn is<TypeTag: CheckedTypeTag>(&self) -> bool {
+        <Self as Borrow<Obj<_>>>::borrow(self).is::<TypeTag>()
+    }
+
+    pub fn downcast_ref<TypeTag: CheckedTypeTag>(&self) -> Option<&Obj<'r, TypeTag>> {
+        <Self as Borrow<Obj<_>>>::borrow(self).downcast_ref()
+    }
+
+    /// Converts this object to an object of the type of `TypeTag`, or returns
+    /// it unchanged if it is not one
+    ///
+    /// The converted object, and the objects it holds, keep the lifetime of
+    /// this object's runtime, so they cannot outlive it:
+    ///
+    /// ```compile_fail
+    /// # use lean::lean_types::{
+    /// #     any::AnyObject, array::ObjectArrayTypeTag, object::Object, string::StringTypeTag,
+    /// # };
+    /// fn escape(object: AnyObject<'_>) -> Option<Object<'static, StringTypeTag>> {
+    ///     let mut array = object.downcast::<ObjectArrayTypeTag<StringTypeTag>>().ok()?;
+    ///     array.make_mut().pop()
+    /// }
+    /// ```
+    pub fn downcast<TypeTag: CheckedTypeTag>(self) -> Result<Object<'r, TypeTag>, Self> {
+        if self.is::<TypeTag>() {
+            Ok(unsafe { Object::new(self.into_raw()) })
+        } else {
+            Err(self)
+        }
+    }
+}
@@ -1,17 +1,24 @@
 use std::borrow::Borrow;
+use std::cmp::Ordering;
 use std::error::Error;
+use std::hash::{Hash, Hasher};
 use std::iter::FusedIterator;
 use std::marker::PhantomData;
+use std::mem;
+use std::ptr;
 use std::slice;
 
 use lean_sys::{
-    b_lean_obj_arg, lean_alloc_array, lean_array_cptr, lean_array_size, lean_box_float,
-    lean_box_float32, lean_box_uint32, lean_box_uint64, lean_box_usize, lean_object,
-    lean_unbox_float, lean_unbox_float32, lean_unbox_uint32, lean_unbox_uint64, lean_unbox_usize,
+    b_lean_obj_arg, lean_alloc_array, lean_array_cptr, lean_array_push, lean_array_set_size,
+    lean_array_size, lean_box, lean_box_float, lean_box_float32, lean_box_uint32, lean_box_uint64,
+    lean_box_usize, lean_copy_expand_array, lean_dec, lean_obj_arg, lean_object,
+    lean_object_byte_size, lean_unbox, lean_unbox_float, lean_unbox_float32, lean_unbox_uint32,
+    lean_unbox_uint64, lean_unbox_usize,
 };
 
 use super::{
-    Owner, Reference,
+    LeanFromIterator, Owner, Reference,
+    any::{AnyObj, CheckedTypeTag, LeanTypeTag, ObjectKind},
     object::{Obj, Object},
 };
 use crate::{Minimal, Modules, Runtime};
@@ -23,23 +30,65 @@ use crate::{Minimal, Modules, Runtime};
 ///
 /// Implementations of this trait must not mutate array elements nor allow array
 /// elements to be mutated by external code.
-pub unsafe trait LeanArrayTypeTag {
-    type Input;
-    type Output;
+///
+/// Element types are branded with the lifetime `'r` of the runtime that the
+/// array belongs to, so that elements cannot outlive the array's runtime.
+pub unsafe trait LeanArrayTypeTag: LeanTypeTag {
+    type Input<'r>;
+    type Output<'a, 'r>
+    where
+        Self: 'a,
+        'r: 'a;
 
     /// Create an owned Lean object (or a scalar) to be stored in an array
-    fn into_element(input: Self::Input) -> *mut lean_object;
+    fn into_element(input: Self::Input<'_>) -> *mut lean_object;
 
     /// View a borrowed Lean array element as the output type
     ///
     /// # Safety
     ///
-    /// `element` must point to a valid object of the expected type, and the
-    /// object must live as long as the return value.
-    unsafe fn from_element(element: b_lean_obj_arg) -> Self::Output;
+    /// `element` must point to a valid object of the expected type that
+    /// belongs to a runtime that outlives `'r`, and the object must stay
+    /// borrowed for `'a`.
+    unsafe fn from_element<'a, 'r>(element: b_lean_obj_arg) -> Self::Output<'a, 'r>
+    where
+        Self: 'a,
+        'r: 'a;
+
+    /// Take ownership of a Lean array element, such as an element removed
+    /// from an array, and convert it back to the input type
+    ///
+    /// # Safety
+    ///
+    /// `element` must point to a valid object of the expected type that
+    /// belongs to a runtime that outlives `'r`.
+    unsafe fn take_element<'r>(element: lean_obj_arg) -> Self::Input<'r>;
+}
+
+/// An element type tag whose elements can be recognized at runtime
+///
+/// # Safety
+///
+/// Implementations must only accept elements that
+/// [`LeanArrayTypeTag::from_element()`] and
+/// [`LeanArrayTypeTag::take_element()`] can convert.
+pub unsafe trait CheckedArrayTypeTag: LeanArrayTypeTag {
+    /// Whether `element` is a boxed element of this type
+    fn matches_element(element: &AnyObj<'_>) -> bool;
+}
+
+/// Whether `object` is an array whose elements all match `TypeTag`
+fn matches_array<TypeTag: CheckedArrayTypeTag>(object: &AnyObj<'_>) -> bool {
+    object.object_kind() == ObjectKind::Array
+        && unsafe {
+            let array = object.as_mut_raw();
+            slice::from_raw_parts(lean_array_cptr(array).cast_const(), lean_array_size(array))
+                .iter()
+                .all(|&element| TypeTag::matches_element(Obj::from_raw(element)))
+        }
 }
 
-impl<TypeTag: LeanArrayTypeTag> Obj<TypeTag> {
+impl<'r, TypeTag: LeanArrayTypeTag> Obj<'r, TypeTag> {
     fn as_slice(&self) -> &[*mut lean_object] {
         unsafe {
             let array = self.as_mut_raw();
@@ -51,21 +100,22 @@ impl<TypeTag: LeanArrayTypeTag> Obj<TypeTag> {
 
     pub fn iter(
         &self,
-    ) -> impl ExactSizeIterator<Item = TypeTag::Output> + DoubleEndedIterator + FusedIterator {
+    ) -> impl ExactSizeIterator<Item = TypeTag::Output<'_, 'r>> + DoubleEndedIterator + FusedIterator
+    {
         self.as_slice()
             .iter()
-            .map(|item| unsafe { TypeTag::from_element(*item) })
+            .map(|&item| unsafe { TypeTag::from_element(item) })
     }
 }
 
-impl<TypeTag: LeanArrayTypeTag> Object<TypeTag> {
+impl<'r, TypeTag: LeanArrayTypeTag> Object<'r, TypeTag> {
     pub fn from_exact_size_iterator<
         R: Minimal,
         M: Modules,
-        T: Into<TypeTag::Input>,
+        T: Into<TypeTag::Input<'r>>,
         I: IntoIterator<Item = T>,
     >(
-        _runtime: &Runtime<R, M>,
+        _runtime: &'r Runtime<R, M>,
         data: I,
     ) -> Self
     where
@@ -87,142 +137,682 @@ impl<TypeTag: LeanArrayTypeTag> Object<TypeTag> {
 
     pub fn iter(
         &self,
-    ) -> impl ExactSizeIterator<Item = TypeTag::Output> + DoubleEndedIterator + FusedIterator {
+    ) -> impl ExactSizeIterator<Item = TypeTag::Output<'_, 'r>> + DoubleEndedIterator + FusedIterator
+    {
         <Self as Borrow<Obj<_>>>::borrow(self).iter()
     }
+
+    /// Mutably view this array, unless it is shared with other owners
+    pub fn try_unique(&mut self) -> Option<ArrayMut<'_, 'r, TypeTag>> {
+        if self.is_exclusive() {
+            Some(ArrayMut { object: self })
+        } else {
+            None
+        }
+    }
+
+    /// Mutably view this array, first replacing it with a copy if it is shared
+    /// with other owners
+    pub fn make_mut(&mut self) -> ArrayMut<'_, 'r, TypeTag> {
+        if !self.is_exclusive() {
+            unsafe {
+                let array = lean_copy_expand_array(self.as_mut_raw(), false);
+                ptr::write(self, Self::new(array));
+            }
+        }
+        ArrayMut { object: self }
+    }
 }
 
-pub enum U32ArrayTypeTag {}
+impl<'r, TypeTag: LeanArrayTypeTag, T: Into<TypeTag::Input<'r>>> LeanFromIterator<'r, T>
+    for Object<'r, TypeTag>
+{
+    fn from_lean_iter<R: Minimal, M: Modules, I: IntoIterator<Item = T>>(
+        runtime: &'r Runtime<R, M>,
+        iter: I,
+    ) -> Self {
+        let iterator = iter.into_iter();
+        let mut builder = ArrayBuilder::with_capacity(runtime, iterator.size_hint().0);
+        builder.extend(iterator);
+        builder.build()
+    }
+}
 
-unsafe impl LeanArrayTypeTag for U32ArrayTypeTag {
-    type Input = u32;
-    type Output = Self::Input;
+/// A builder for an array of unknown length
+///
+/// The array starts with a capacity hint and grows as needed.
+pub struct ArrayBuilder<'r, TypeTag: LeanArrayTypeTag> {
+    object: Object<'r, TypeTag>,
+}
 
-    fn into_element(input: Self::Input) -> *mut lean_object {
-        unsafe { lean_box_uint32(input) }
+impl<'r, TypeTag: LeanArrayTypeTag> ArrayBuilder<'r, TypeTag> {
+    pub fn new<R: Minimal, M: Modules>(runtime: &'r Runtime<R, M>) -> Self {
+        Self::with_capacity(runtime, 0)
     }
 
-    unsafe fn from_element(element: b_lean_obj_arg) -> Self::Output {
-        unsafe { lean_unbox_uint32(element) }
+    pub fn with_capacity<R: Minimal, M: Modules>(
+        _runtime: &'r Runtime<R, M>,
+        capacity: usize,
+    ) -> Self {
+        let object = unsafe { lean_alloc_array(0, capacity) };
+        Self {
+            object: unsafe { Object::new(object) },
+        }
     }
-}
 
-pub type U32Arr = Obj<U32ArrayTypeTag>;
-pub type U32Array = Object<U32ArrayTypeTag>;
+    pub fn len(&self) -> usize {
+        unsafe { lean_array_size(self.object.as_mut_raw()) }
+    }
 
-pub struct Integer32ArrayTypeTag<T: Into<i32> + TryFrom<i32>>(PhantomData<T>)
-where
-    <T as TryFrom<i32>>::Error: Error;
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 
-unsafe impl<T: Into<i32> + TryFrom<i32>> LeanArrayTypeTag for Integer32ArrayTypeTag<T>
-where
-    <T as TryFrom<i32>>::Error: Error,
+    pub fn push<T: Into<TypeTag::Input<'r>>>(&mut self, value: T) -> &mut Self {
+        self.array_mut().push(value);
+        self
+    }
+
+    pub fn build(self) -> Object<'r, TypeTag> {
+        self.object
+    }
+
+    fn array_mut(&mut self) -> ArrayMut<'_, 'r, TypeTag> {
+        // The builder is the array's only owner
+        ArrayMut {
+            object: &mut self.object,
+        }
+    }
+}
+
+impl<'r, TypeTag: LeanArrayTypeTag, T: Into<TypeTag::Input<'r>>> Extend<T>
+    for ArrayBuilder<'r, TypeTag>
 {
-    type Input = T;
-    type Output = Self::Input;
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut array = self.array_mut();
+        for value in iter {
+            array.push(value);
+        }
+    }
+}
 
-    fn into_element(input: Self::Input) -> *mut lean_object {
-        let u32_value = input.into() as u32;
-        unsafe { lean_box_uint32(u32_value) }
+/// A mutable view of an exclusively-owned array
+pub struct ArrayMut<'a, 'r, TypeTag: LeanArrayTypeTag> {
+    object: &'a mut Object<'r, TypeTag>,
+}
+
+impl<'r, TypeTag: LeanArrayTypeTag> ArrayMut<'_, 'r, TypeTag> {
+    fn as_mut_slice(&mut self) -> &mut [*mut lean_object] {
+        unsafe {
+            let array = self.object.as_mut_raw();
+            slice::from_raw_parts_mut(lean_array_cptr(array), lean_array_size(array))
+        }
     }
 
-    unsafe fn from_element(element: b_lean_obj_arg) -> Self::Output {
-        let u32_value = unsafe { lean_unbox_uint32(element) };
-        (u32_value as i32).try_into().unwrap()
+    pub fn len(&self) -> usize {
+        unsafe { lean_array_size(self.object.as_mut_raw()) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter(
+        &self,
+    ) -> impl ExactSizeIterator<Item = TypeTag::Output<'_, 'r>> + DoubleEndedIterator + FusedIterator
+    {
+        self.object.iter()
+    }
+
+    /// Replaces the element at `index`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set<T: Into<TypeTag::Input<'r>>>(&mut self, index: usize, value: T) {
+        let element = TypeTag::into_element(value.into());
+        let old_element = std::mem::replace(&mut self.as_mut_slice()[index], element);
+        unsafe { lean_dec(old_element) };
+    }
+
+    /// Appends an element, growing the array's capacity if needed
+    pub fn push<T: Into<TypeTag::Input<'r>>>(&mut self, value: T) {
+        let element = TypeTag::into_element(value.into());
+        unsafe {
+            // `lean_array_push()` consumes the array and may reallocate it
+            let array = lean_array_push(self.object.as_mut_raw(), element);
+            ptr::write(self.object, Object::new(array));
+        }
+    }
+
+    /// Removes the last element, unless the array is empty
+    pub fn pop(&mut self) -> Option<TypeTag::Input<'r>> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+        unsafe {
+            let array = self.object.as_mut_raw();
+            let element = *lean_array_cptr(array).add(len - 1);
+            lean_array_set_size(array, len - 1);
+            Some(TypeTag::take_element(element))
+        }
+    }
+
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.as_mut_slice().swap(a, b);
+    }
+}
+
+/// The element type tag of arrays of Lean objects, where `T` is the type tag
+/// of the elements
+///
+/// This allows for arrays of strings (`Array String`), nested arrays and arrays
+/// of structures. Elements are added as owned objects and viewed as borrowed
+/// objects.
+///
+/// The elements belong to the runtime of the array.
+pub struct ObjectArrayTypeTag<T>(PhantomData<T>);
+
+impl<T> LeanTypeTag for ObjectArrayTypeTag<T> {
+    fn accepts_kind(kind: ObjectKind) -> bool {
+        kind == ObjectKind::Array
+    }
+}
+
+unsafe impl<T: LeanTypeTag> LeanArrayTypeTag for ObjectArrayTypeTag<T> {
+    type Input<'r> = Object<'r, T>;
+    type Output<'a, 'r>
+        = &'a Obj<'r, T>
+    where
+        Self: 'a,
+        'r: 'a;
+
+    fn into_element(input: Self::Input<'_>) -> *mut lean_object {
+        input.into_raw()
+    }
+
+    unsafe fn from_element<'a, 'r>(element: b_lean_obj_arg) -> Self::Output<'a, 'r>
+    where
+        Self: 'a,
+        'r: 'a,
+    {
+        unsafe { Obj::from_raw(element) }
+    }
+
+    unsafe fn take_element<'r>(element: lean_obj_arg) -> Self::Input<'r> {
+        unsafe { Object::new(element) }
+    }
+}
+
+unsafe impl<T: CheckedTypeTag> CheckedArrayTypeTag for ObjectArrayTypeTag<T> {
+    fn matches_element(element: &AnyObj<'_>) -> bool {
+        T::matches(element)
+    }
+}
+
+unsafe impl<T: CheckedTypeTag> CheckedTypeTag for ObjectArrayTypeTag<T> {
+    fn matches(object: &AnyObj<'_>) -> bool {
+        matches_array::<Self>(object)
     }
 }
 
-pub type Integer32Arr<T> = Obj<Integer32ArrayTypeTag<T>>;
-pub type Integer32Array<T> = Object<Integer32ArrayTypeTag<T>>;
+pub type ObjectArr<'r, T> = Obj<'r, ObjectArrayTypeTag<T>>;
+pub type ObjectArray<'r, T> = Object<'r, ObjectArrayTypeTag<T>>;
 
-pub enum U64ArrayTypeTag {}
+/// A trait implemented by the element type tags of fixed-width Lean scalar
+/// types
+///
+/// Arrays of such elements compare and hash element-wise, like Lean's
+/// instances for `Array α` do.
+pub trait ScalarArrayTypeTag: LeanArrayTypeTag {}
+
+/// Iterates over the elements of a scalar array, which do not depend on the
+/// lifetime of the runtime, so the array can be viewed with a shorter one
+fn scalars<'a, TypeTag: ScalarArrayTypeTag>(
+    array: &'a Obj<'a, TypeTag>,
+) -> impl ExactSizeIterator<Item = TypeTag::Output<'a, 'a>> {
+    array.iter()
+}
 
-unsafe impl LeanArrayTypeTag for U64ArrayTypeTag {
-    type Input = u64;
-    type Output = Self::Input;
+impl<TypeTag: ScalarArrayTypeTag> PartialEq for Obj<'_, TypeTag>
+where
+    for<'a> TypeTag::Output<'a, 'a>: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        scalars(self).eq(scalars(other))
+    }
+}
+
+impl<TypeTag: ScalarArrayTypeTag> Eq for Obj<'_, TypeTag> where for<'a> TypeTag::Output<'a, 'a>: Eq {}
 
-    fn into_element(input: Self::Input) -> *mut lean_object {
-        unsafe { lean_box_uint64(input) }
+impl<TypeTag: ScalarArrayTypeTag> PartialOrd for Obj<'_, TypeTag>
+where
+    for<'a> TypeTag::Output<'a, 'a>: PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        scalars(self).partial_cmp(scalars(other))
     }
+}
 
-    unsafe fn from_element(element: b_lean_obj_arg) -> Self::Output {
-        unsafe { lean_unbox_uint64(element) }
+impl<TypeTag: ScalarArrayTypeTag> Ord for Obj<'_, TypeTag>
+where
+    for<'a> TypeTag::Output<'a, 'a>: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        scalars(self).cmp(scalars(other))
     }
 }
 
-pub type U64Arr = Obj<U64ArrayTypeTag>;
-pub type U64Array = Object<U64ArrayTypeTag>;
+impl<TypeTag: ScalarArrayTypeTag> Hash for Obj<'_, TypeTag>
+where
+    for<'a> TypeTag::Output<'a, 'a>: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let iterator = scalars(self);
+        state.write_usize(iterator.len());
+        iterator.for_each(|element| element.hash(state));
+    }
+}
 
-pub enum Integer64ArrayTypeTag {}
+impl<TypeTag: ScalarArrayTypeTag> PartialEq for Object<'_, TypeTag>
+where
+    for<'a> TypeTag::Output<'a, 'a>: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        <Self as Borrow<Obj<_>>>::borrow(self) == <Self as Borrow<Obj<_>>>::borrow(other)
+    }
+}
 
-unsafe impl LeanArrayTypeTag for Integer64ArrayTypeTag {
-    type Input = i64;
-    type Output = Self::Input;
+impl<TypeTag: ScalarArrayTypeTag> Eq for Object<'_, TypeTag> where
+    for<'a> TypeTag::Output<'a, 'a>: Eq
+{
+}
 
-    fn into_element(input: Self::Input) -> *mut lean_object {
-        let u64_value = input as u64;
-        unsafe { lean_box_uint64(u64_value) }
+impl<TypeTag: ScalarArrayTypeTag> PartialOrd for Object<'_, TypeTag>
+where
+    for<'a> TypeTag::Output<'a, 'a>: PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        <Self as Borrow<Obj<_>>>::borrow(self).partial_cmp(<Self as Borrow<Obj<_>>>::borrow(other))
     }
+}
 
-    unsafe fn from_element(element: b_lean_obj_arg) -> Self::Output {
-        let u64_value = unsafe { lean_unbox_uint64(element) };
-        u64_value.try_into().unwrap()
+impl<TypeTag: ScalarArrayTypeTag> Ord for Object<'_, TypeTag>
+where
+    for<'a> TypeTag::Output<'a, 'a>: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        <Self as Borrow<Obj<_>>>::borrow(self).cmp(<Self as Borrow<Obj<_>>>::borrow(other))
     }
 }
 
-pub type Integer64Arr = Obj<Integer64ArrayTypeTag>;
-pub type Integer64Array = Object<Integer64ArrayTypeTag>;
+impl<TypeTag: ScalarArrayTypeTag> Hash for Object<'_, TypeTag>
+where
+    for<'a> TypeTag::Output<'a, 'a>: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        <Self as Borrow<Obj<_>>>::borrow(self).hash(state);
+    }
+}
 
-pub enum UsizeArrayTypeTag {}
+/// Whether `element` is a scalar no greater than `max`, such as a boxed `UInt8`
+fn is_small_scalar(element: &AnyObj<'_>, max: usize) -> bool {
+    matches!(element.object_kind(), ObjectKind::Scalar(value) if value <= max)
+}
 
-unsafe impl LeanArrayTypeTag for UsizeArrayTypeTag {
-    type Input = usize;
-    type Output = Self::Input;
+/// Whether `element` is a constructor object without boxed fields and with a
+/// scalar field of `size` bytes, such as a boxed `UInt64`, `USize` or `Float`
+///
+/// Lean does not record the size of scalar fields, so the size of the object
+/// is checked instead, which Lean's small object allocator rounds up to a
+/// multiple of `LEAN_OBJECT_SIZE_DELTA` bytes. Boxed scalars of the same width,
+/// such as `UInt64`, `Int64`, `USize` and `Float` on 64-bit platforms, cannot
+/// be told apart, nor can a boxed `Float32` and an 8-byte boxed scalar when
+/// rounding makes them the same size. Reading such an element with the wrong
+/// type stays within the object but yields a meaningless value.
+fn is_boxed_scalar(element: &AnyObj<'_>, size: usize) -> bool {
+    const LEAN_OBJECT_SIZE_DELTA: usize = 8;
+    let min_byte_size = mem::size_of::<lean_object>() + size;
+    matches!(
+        element.object_kind(),
+        ObjectKind::Ctor {
+            tag: 0,
+            num_objs: 0
+        }
+    ) && (min_byte_size..=min_byte_size.next_multiple_of(LEAN_OBJECT_SIZE_DELTA))
+        .contains(&unsafe { lean_object_byte_size(element.as_mut_raw()) })
+}
 
-    fn into_element(input: Self::Input) -> *mut lean_object {
-        unsafe { lean_box_usize(input) }
+/// Whether `element` is a boxed `UInt32`, which is a scalar on 64-bit
+/// platforms only
+fn is_boxed_uint32(element: &AnyObj<'_>) -> bool {
+    if mem::size_of::<usize>() > mem::size_of::<u32>() {
+        is_small_scalar(element, u32::MAX as usize)
+    } else {
+        is_boxed_scalar(element, mem::size_of::<u32>())
     }
+}
+
+/// Defines the element type tag of a fixed-width Lean scalar type, which
+/// `$box` and `$unbox` convert to and from its boxed representation and
+/// `$matches` recognizes
+///
+/// Signed integers are boxed as the unsigned integers of the same width, so
+/// they round-trip through their two's complement representation.
+macro_rules! scalar_array_type_tag {
+    ($(#[$attr:meta])* $tag:ident, $arr:ident, $array:ident, $type:ty, $box:expr, $unbox:expr, $matches:expr $(,)?) => {
+        $(#[$attr])*
+        pub enum $tag {}
+
+        impl LeanTypeTag for $tag {
+            fn accepts_kind(kind: ObjectKind) -> bool {
+                kind == ObjectKind::Array
+            }
+        }
+
+        unsafe impl LeanArrayTypeTag for $tag {
+            type Input<'r> = $type;
+            type Output<'a, 'r>
+                = $type
+            where
+                Self: 'a,
+                'r: 'a;
+
+            fn into_element(input: Self::Input<'_>) -> *mut lean_object {
+                let box_element: fn($type) -> *mut lean_object = $box;
+                box_element(input)
+            }
+
+            unsafe fn from_element<'a, 'r>(element: b_lean_obj_arg) -> Self::Output<'a, 'r>
+            where
+                Self: 'a,
+                'r: 'a,
+            {
+                let unbox_element: unsafe fn(*mut lean_object) -> $type = $unbox;
+                unsafe { unbox_element(element) }
+            }
+
+            unsafe fn take_element<'r>(element: lean_obj_arg) -> Self::Input<'r> {
+                unsafe {
+                    let input = Self::from_element(element);
+                    lean_dec(element);
+                    input
+                }
+            }
+        }
+
+        unsafe impl CheckedArrayTypeTag for $tag {
+            fn matches_element(element: &AnyObj<'_>) -> bool {
+                let matches_element: fn(&AnyObj<'_>) -> bool = $matches;
+                matches_element(element)
+            }
+        }
+
+        unsafe impl CheckedTypeTag for $tag {
+            fn matches(object: &AnyObj<'_>) -> bool {
+                matches_array::<Self>(object)
+            }
+        }
+
+        impl ScalarArrayTypeTag for $tag {}
+
+        pub type $arr<'r> = Obj<'r, $tag>;
+        pub type $array<'r> = Object<'r, $tag>;
+    };
+}
 
-    unsafe fn from_element(element: b_lean_obj_arg) -> Self::Output {
-        unsafe { lean_unbox_usize(element) }
+scalar_array_type_tag!(
+    /// The element type tag of Lean's `UInt8`
+    U8ArrayTypeTag,
+    U8Arr,
+    U8Array,
+    u8,
+    |value| unsafe { lean_box(value.into()) },
+    |element| unsafe { lean_unbox(element) as u8 },
+    |element| is_small_scalar(element, u8::MAX.into()),
+);
+
+scalar_array_type_tag!(
+    /// The element type tag of Lean's `Int8`
+    Integer8ArrayTypeTag,
+    Integer8Arr,
+    Integer8Array,
+    i8,
+    |value| unsafe { lean_box(value as u8 as usize) },
+    |element| unsafe { lean_unbox(element) as u8 as i8 },
+    |element| is_small_scalar(element, u8::MAX.into()),
+);
+
+scalar_array_type_tag!(
+    /// The element type tag of Lean's `UInt16`
+    U16ArrayTypeTag,
+    U16Arr,
+    U16Array,
+    u16,
+    |value| unsafe { lean_box(value.into()) },
+    |element| unsafe { lean_unbox(element) as u16 },
+    |element| is_small_scalar(element, u16::MAX.into()),
+);
+
+scalar_array_type_tag!(
+    /// The element type tag of Lean's `Int16`
+    Integer16ArrayTypeTag,
+    Integer16Arr,
+    Integer16Array,
+    i16,
+    |value| unsafe { lean_box(value as u16 as usize) },
+    |element| unsafe { lean_unbox(element) as u16 as i16 },
+    |element| is_small_scalar(element, u16::MAX.into()),
+);
+
+scalar_array_type_tag!(
+    /// The element type tag of Lean's `UInt32`
+    U32ArrayTypeTag,
+    U32Arr,
+    U32Array,
+    u32,
+    |value| unsafe { lean_box_uint32(value) },
+    |element| unsafe { lean_unbox_uint32(element) },
+    is_boxed_uint32,
+);
+
+/// The element type tag of Lean's `Int32`, viewed as a Rust type `T` that
+/// converts to and from `i32`
+///
+/// Iterating panics on values that `T` cannot represent, whereas
+/// [`Obj::try_iter()`] returns conversion errors.
+pub struct Integer32ArrayTypeTag<T: Into<i32> + TryFrom<i32>>(PhantomData<T>)
+where
+    <T as TryFrom<i32>>::Error: Error;
+
+impl<T: Into<i32> + TryFrom<i32>> LeanTypeTag for Integer32ArrayTypeTag<T>
+where
+    <T as TryFrom<i32>>::Error: Error,
+{
+    fn accepts_kind(kind: ObjectKind) -> bool {
+        kind == ObjectKind::Array
     }
 }
 
-pub type UsizeArr = Obj<UsizeArrayTypeTag>;
-pub type UsizeArray = Object<UsizeArrayTypeTag>;
+unsafe impl<T: Into<i32> + TryFrom<i32>> LeanArrayTypeTag for Integer32ArrayTypeTag<T>
+where
+    <T as TryFrom<i32>>::Error: Error,
+{
+    type Input<'r> = T;
+    type Output<'a, 'r>
+        = T
+    where
+        T: 'a,
+        'r: 'a;
 
-pub enum F32ArrayTypeTag {}
+    fn into_element(input: Self::Input<'_>) -> *mut lean_object {
+        let u32_value = input.into() as u32;
+        unsafe { lean_box_uint32(u32_value) }
+    }
 
-unsafe impl LeanArrayTypeTag for F32ArrayTypeTag {
-    type Input = f32;
-    type Output = Self::Input;
+    unsafe fn from_element<'a, 'r>(element: b_lean_obj_arg) -> Self::Output<'a, 'r>
+    where
+        Self: 'a,
+        'r: 'a,
+    {
+        let u32_value = unsafe { lean_unbox_uint32(element) };
+        (u32_value as i32)
+            .try_into()
+            .expect("Lean array element out of range for the element type")
+    }
 
-    fn into_element(input: Self::Input) -> *mut lean_object {
-        unsafe { lean_box_float32(input) }
+    unsafe fn take_element<'r>(element: lean_obj_arg) -> Self::Input<'r> {
+        unsafe {
+            let input = Self::from_element(element);
+            lean_dec(element);
+            input
+        }
     }
+}
 
-    unsafe fn from_element(element: b_lean_obj_arg) -> Self::Output {
-        unsafe { lean_unbox_float32(element) }
+unsafe impl<T: Into<i32> + TryFrom<i32>> CheckedArrayTypeTag for Integer32ArrayTypeTag<T>
+where
+    <T as TryFrom<i32>>::Error: Error,
+{
+    fn matches_element(element: &AnyObj<'_>) -> bool {
+        is_boxed_uint32(element)
     }
 }
 
-pub type F32Arr = Obj<F32ArrayTypeTag>;
-pub type F32Array = Object<F32ArrayTypeTag>;
+unsafe impl<T: Into<i32> + TryFrom<i32>> CheckedTypeTag for Integer32ArrayTypeTag<T>
+where
+    <T as TryFrom<i32>>::Error: Error,
+{
+    fn matches(object: &AnyObj<'_>) -> bool {
+        matches_array::<Self>(object)
+    }
+}
 
-pub enum F64ArrayTypeTag {}
+impl<T: Into<i32> + TryFrom<i32>> ScalarArrayTypeTag for Integer32ArrayTypeTag<T> where
+    <T as TryFrom<i32>>::Error: Error
+{
+}
 
-unsafe impl LeanArrayTypeTag for F64ArrayTypeTag {
-    type Input = f64;
-    type Output = Self::Input;
+pub type Integer32Arr<'r, T> = Obj<'r, Integer32ArrayTypeTag<T>>;
+pub type Integer32Array<'r, T> = Object<'r, Integer32ArrayTypeTag<T>>;
 
-    fn into_element(input: Self::Input) -> *mut lean_object {
-        unsafe { lean_box_float(input) }
+impl<T: Into<i32> + TryFrom<i32>> Obj<'_, Integer32ArrayTypeTag<T>>
+where
+    <T as TryFrom<i32>>::Error: Error,
+{
+    pub fn try_iter(
+        &self,
+    ) -> impl ExactSizeIterator<Item = Result<T, <T as TryFrom<i32>>::Error>>
+    + DoubleEndedIterator
+    + FusedIterator {
+        self.as_slice()
+            .iter()
+            .map(|element| (unsafe { lean_unbox_uint32(*element) } as i32).try_into())
     }
+}
 
-    unsafe fn from_element(element: b_lean_obj_arg) -> Self::Output {
-        unsafe { lean_unbox_float(element) }
+impl<T: Into<i32> + TryFrom<i32>> Object<'_, Integer32ArrayTypeTag<T>>
+where
+    <T as TryFrom<i32>>::Error: Error,
+{
+    pub fn try_iter(
+        &self,
+    ) -> impl ExactSizeIterator<Item = Result<T, <T as TryFrom<i32>>::Error>>
+    + DoubleEndedIterator
+    + FusedIterator {
+        <Self as Borrow<Obj<_>>>::borrow(self).try_iter()
     }
 }
 
-pub type F64Arr = Obj<F64ArrayTypeTag>;
-pub type F64Array = Object<F64ArrayTypeTag>;
+scalar_array_type_tag!(
+    /// The element type tag of Lean's `UInt64`
+    U64ArrayTypeTag,
+    U64Arr,
+    U64Array,
+    u64,
+    |value| unsafe { lean_box_uint64(value) },
+    |element| unsafe { lean_unbox_uint64(element) },
+    |element| is_boxed_scalar(element, mem::size_of::<u64>()),
+);
+
+scalar_array_type_tag!(
+    /// The element type tag of Lean's `Int64`
+    Integer64ArrayTypeTag,
+    Integer64Arr,
+    Integer64Array,
+    i64,
+    |value| unsafe { lean_box_uint64(value as u64) },
+    |element| unsafe { lean_unbox_uint64(element) as i64 },
+    |element| is_boxed_scalar(element, mem::size_of::<i64>()),
+);
+
+scalar_array_type_tag!(
+    /// The element type tag of Lean's `USize`
+    UsizeArrayTypeTag,
+    UsizeArr,
+    UsizeArray,
+    usize,
+    |value| unsafe { lean_box_usize(value) },
+    |element| unsafe { lean_unbox_usize(element) },
+    |element| is_boxed_scalar(element, mem::size_of::<usize>()),
+);
+
+scalar_array_type_tag!(
+    /// The element type tag of Lean's `ISize`
+    IsizeArrayTypeTag,
+    IsizeArr,
+    IsizeArray,
+    isize,
+    |value| unsafe { lean_box_usize(value as usize) },
+    |element| unsafe { lean_unbox_usize(element) as isize },
+    |element| is_boxed_scalar(element, mem::size_of::<isize>()),
+);
+
+scalar_array_type_tag!(
+    /// The element type tag of Lean's `Float32`
+    F32ArrayTypeTag,
+    F32Arr,
+    F32Array,
+    f32,
+    |value| unsafe { lean_box_float32(value) },
+    |element| unsafe { lean_unbox_float32(element) },
+    |element| is_boxed_scalar(element, mem::size_of::<f32>()),
+);
+
+scalar_array_type_tag!(
+    /// The element type tag of Lean's `Float`
+    F64ArrayTypeTag,
+    F64Arr,
+    F64Array,
+    f64,
+    |value| unsafe { lean_box_float(value) },
+    |element| unsafe { lean_unbox_float(element) },
+    |element| is_boxed_scalar(element, mem::size_of::<f64>()),
+);
+
+scalar_array_type_tag!(
+    /// The element type tag of Lean's `Bool`
+    BoolArrayTypeTag,
+    BoolArr,
+    BoolArray,
+    bool,
+    |value| unsafe { lean_box(value.into()) },
+    |element| unsafe { lean_unbox(element) != 0 },
+    |element| is_small_scalar(element, 1),
+);
+
+scalar_array_type_tag!(
+    /// The element type tag of Lean's `Char`
+    CharArrayTypeTag,
+    CharArr,
+    CharArray,
+    char,
+    |value| unsafe { lean_box_uint32(value.into()) },
+    // Lean characters are valid Unicode scalar values
+    |element| unsafe { char::from_u32_unchecked(lean_unbox_uint32(element)) },
+    |element| {
+        is_boxed_uint32(element)
+            && char::from_u32(unsafe { lean_unbox_uint32(element.as_mut_raw()) }).is_some()
+    },
+);
@@ -1,19 +1,38 @@
 use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
 use std::iter::FusedIterator;
 use std::mem;
+use std::ptr;
 use std::slice;
 
-use lean_sys::{lean_alloc_sarray, lean_sarray_cptr, lean_sarray_size};
+use lean_sys::{
+    lean_alloc_sarray, lean_byte_array_hash, lean_byte_array_push, lean_copy_byte_array,
+    lean_sarray_cptr, lean_sarray_set_size, lean_sarray_size,
+};
 
 use super::{
-    Owner, Reference,
+    LeanFromIterator, Owner, Reference,
+    any::{AnyObj, CheckedTypeTag, LeanTypeTag, ObjectKind},
     object::{Obj, Object},
 };
 use crate::{Minimal, Modules, Runtime};
 
 pub enum ByteArrayTypeTag {}
 
-impl Obj<ByteArrayTypeTag> {
+impl LeanTypeTag for ByteArrayTypeTag {
+    fn accepts_kind(kind: ObjectKind) -> bool {
+        kind == ObjectKind::ScalarArray { elem_size: 1 }
+    }
+}
+
+unsafe impl CheckedTypeTag for ByteArrayTypeTag {
+    fn matches(object: &AnyObj<'_>) -> bool {
+        Self::accepts_kind(object.object_kind())
+    }
+}
+
+impl Obj<'_, ByteArrayTypeTag> {
     pub fn as_slice(&self) -> &[u8] {
         unsafe {
             let array = self.as_mut_raw();
@@ -28,14 +47,14 @@ impl Obj<ByteArrayTypeTag> {
     }
 }
 
-impl Object<ByteArrayTypeTag> {
+impl<'r> Object<'r, ByteArrayTypeTag> {
     pub fn from_exact_size_iterator<
         R: Minimal,
         M: Modules,
         T: Into<u8>,
         I: IntoIterator<Item = T>,
     >(
-        _runtime: &Runtime<R, M>,
+        _runtime: &'r Runtime<R, M>,
         data: I,
     ) -> Self
     where
@@ -62,7 +81,204 @@ impl Object<ByteArrayTypeTag> {
     pub fn iter(&self) -> impl ExactSizeIterator<Item = u8> + DoubleEndedIterator + FusedIterator {
         <Self as Borrow<Obj<_>>>::borrow(self).iter()
     }
+
+    /// Mutably view this array, unless it is shared with other owners
+    pub fn try_unique(&mut self) -> Option<ByteArrayMut<'_, 'r>> {
+        if self.is_exclusive() {
+            Some(ByteArrayMut { object: self })
+        } else {
+            None
+        }
+    }
+
+    /// Mutably view this array, first replacing it with a copy if it is shared
+    /// with other owners
+    pub fn make_mut(&mut self) -> ByteArrayMut<'_, 'r> {
+        if !self.is_exclusive() {
+            unsafe {
+                let array = lean_copy_byte_array(self.as_mut_raw());
+                ptr::write(self, Self::new(array));
+            }
+        }
+        ByteArrayMut { object: self }
+    }
+}
+
+impl<'r, T: Into<u8>> LeanFromIterator<'r, T> for Object<'r, ByteArrayTypeTag> {
+    fn from_lean_iter<R: Minimal, M: Modules, I: IntoIterator<Item = T>>(
+        runtime: &'r Runtime<R, M>,
+        iter: I,
+    ) -> Self {
+        let iterator = iter.into_iter();
+        let mut builder = ByteArrayBuilder::with_capacity(runtime, iterator.size_hint().0);
+        builder.extend(iterator);
+        builder.build()
+    }
+}
+
+/// A builder for a `ByteArray` of unknown length
+///
+/// The array starts with a capacity hint and grows as needed.
+pub struct ByteArrayBuilder<'r> {
+    object: Object<'r, ByteArrayTypeTag>,
+}
+
+impl<'r> ByteArrayBuilder<'r> {
+    pub fn new<R: Minimal, M: Modules>(runtime: &'r Runtime<R, M>) -> Self {
+        Self::with_capacity(runtime, 0)
+    }
+
+    pub fn with_capacity<R: Minimal, M: Modules>(
+        _runtime: &'r Runtime<R, M>,
+        capacity: usize,
+    ) -> Self {
+        let object = unsafe { lean_alloc_sarray(mem::size_of::<u8>() as u32, 0, capacity) };
+        Self {
+            object: unsafe { Object::new(object) },
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.object.as_slice().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.object.as_slice().is_empty()
+    }
+
+    pub fn push<T: Into<u8>>(&mut self, value: T) -> &mut Self {
+        self.array_mut().push(value.into());
+        self
+    }
+
+    pub fn build(self) -> Object<'r, ByteArrayTypeTag> {
+        self.object
+    }
+
+    fn array_mut(&mut self) -> ByteArrayMut<'_, 'r> {
+        // The builder is the array's only owner
+        ByteArrayMut {
+            object: &mut self.object,
+        }
+    }
+}
+
+impl<T: Into<u8>> Extend<T> for ByteArrayBuilder<'_> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut array = self.array_mut();
+        for value in iter {
+            array.push(value.into());
+        }
+    }
+}
+
+/// A mutable view of an exclusively-owned `ByteArray`
+pub struct ByteArrayMut<'a, 'r> {
+    object: &'a mut Object<'r, ByteArrayTypeTag>,
+}
+
+impl ByteArrayMut<'_, '_> {
+    pub fn as_slice(&self) -> &[u8] {
+        self.object.as_slice()
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe {
+            let array = self.object.as_mut_raw();
+            slice::from_raw_parts_mut(lean_sarray_cptr(array), lean_sarray_size(array))
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.as_slice().is_empty()
+    }
+
+    /// Replaces the element at `index`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set(&mut self, index: usize, value: u8) {
+        self.as_mut_slice()[index] = value;
+    }
+
+    /// Appends an element, growing the array's capacity if needed
+    pub fn push(&mut self, value: u8) {
+        unsafe {
+            // `lean_byte_array_push()` consumes the array and may reallocate it
+            let array = lean_byte_array_push(self.object.as_mut_raw(), value);
+            ptr::write(self.object, Object::new(array));
+        }
+    }
+
+    /// Removes the last element, unless the array is empty
+    pub fn pop(&mut self) -> Option<u8> {
+        let (&last, _) = self.as_slice().split_last()?;
+        unsafe { lean_sarray_set_size(self.object.as_mut_raw(), self.len() - 1) };
+        Some(last)
+    }
+
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.as_mut_slice().swap(a, b);
+    }
+}
+
+impl PartialEq for Obj<'_, ByteArrayTypeTag> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for Obj<'_, ByteArrayTypeTag> {}
+
+impl PartialOrd for Obj<'_, ByteArrayTypeTag> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Obj<'_, ByteArrayTypeTag> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+/// Feeds the hash of Lean's `Hashable ByteArray` instance to the hasher
+impl Hash for Obj<'_, ByteArrayTypeTag> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(unsafe { lean_byte_array_hash(self.as_mut_raw()) });
+    }
+}
+
+impl PartialEq for Object<'_, ByteArrayTypeTag> {
+    fn eq(&self, other: &Self) -> bool {
+        <Self as Borrow<Obj<_>>>::borrow(self) == <Self as Borrow<Obj<_>>>::borrow(other)
+    }
+}
+
+impl Eq for Object<'_, ByteArrayTypeTag> {}
+
+impl PartialOrd for Object<'_, ByteArrayTypeTag> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Object<'_, ByteArrayTypeTag> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        <Self as Borrow<Obj<_>>>::borrow(self).cmp(<Self as Borrow<Obj<_>>>::borrow(other))
+    }
+}
+
+impl Hash for Object<'_, ByteArrayTypeTag> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        <Self as Borrow<Obj<_>>>::borrow(self).hash(state);
+    }
 }
 
-pub type ByteArr = Obj<ByteArrayTypeTag>;
-pub type ByteArray = Object<ByteArrayTypeTag>;
+pub type ByteArr<'r> = Obj<'r, ByteArrayTypeTag>;
+pub type ByteArray<'r> = Object<'r, ByteArrayTypeTag>;
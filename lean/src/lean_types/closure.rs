@@ -0,0 +1,401 @@
+use std::any::Any;
+use std::borrow::Borrow;
+use std::ffi::{CString, c_void};
+use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Mutex, OnceLock};
+
+use lean_sys::{
+    lean_alloc_closure, lean_alloc_external, lean_apply_1, lean_apply_2, lean_apply_3,
+    lean_apply_4, lean_apply_5, lean_apply_6, lean_apply_7, lean_apply_8, lean_apply_9,
+    lean_apply_10, lean_apply_11, lean_apply_12, lean_apply_13, lean_apply_14, lean_apply_15,
+    lean_apply_16, lean_apply_m, lean_apply_n, lean_closure_arity, lean_closure_num_fixed,
+    lean_closure_set, lean_dec, lean_external_class, lean_get_external_data, lean_inc,
+    lean_internal_panic, lean_obj_arg, lean_obj_res, lean_register_external_class,
+};
+
+use super::{
+    LeanValue, Owner, Reference,
+    any::{LeanTypeTag, ObjectKind},
+    external::ExternalClass,
+    object::{Obj, Object},
+};
+use crate::{Minimal, Modules, Runtime};
+
+/// A tuple of arguments to apply a Lean closure to
+///
+/// # Safety
+///
+/// Implementations must pass exactly the arguments of the tuple to the
+/// closure, transferring ownership of each of them.
+pub unsafe trait LeanFnArgs {
+    /// Applies `f` to the arguments
+    ///
+    /// # Safety
+    ///
+    /// `f` must be an owned closure object that accepts arguments of these
+    /// types.
+    unsafe fn apply(self, f: lean_obj_arg) -> lean_obj_res;
+}
+
+macro_rules! impl_lean_fn_args {
+    ($lean_apply:ident; $($arg:ident),+) => {
+        unsafe impl<$($arg: LeanValue),+> LeanFnArgs for ($($arg,)+) {
+            unsafe fn apply(self, f: lean_obj_arg) -> lean_obj_res {
+                #[allow(non_snake_case)]
+                let ($($arg,)+) = self;
+                unsafe { $lean_apply(f, $($arg.into_boxed()),+) }
+            }
+        }
+    };
+}
+
+impl_lean_fn_args!(lean_apply_1; A1);
+impl_lean_fn_args!(lean_apply_2; A1, A2);
+impl_lean_fn_args!(lean_apply_3; A1, A2, A3);
+impl_lean_fn_args!(lean_apply_4; A1, A2, A3, A4);
+impl_lean_fn_args!(lean_apply_5; A1, A2, A3, A4, A5);
+impl_lean_fn_args!(lean_apply_6; A1, A2, A3, A4, A5, A6);
+impl_lean_fn_args!(lean_apply_7; A1, A2, A3, A4, A5, A6, A7);
+impl_lean_fn_args!(lean_apply_8; A1, A2, A3, A4, A5, A6, A7, A8);
+impl_lean_fn_args!(lean_apply_9; A1, A2, A3, A4, A5, A6, A7, A8, A9);
+impl_lean_fn_args!(lean_apply_10; A1, A2, A3, A4, A5, A6, A7, A8, A9, A10);
+impl_lean_fn_args!(lean_apply_11; A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11);
+impl_lean_fn_args!(lean_apply_12; A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12);
+impl_lean_fn_args!(lean_apply_13; A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13);
+impl_lean_fn_args!(lean_apply_14; A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14);
+impl_lean_fn_args!(lean_apply_15; A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15);
+impl_lean_fn_args!(lean_apply_16; A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15, A16);
+
+/// Arrays of arguments of the same type may be arbitrarily long
+unsafe impl<T: LeanValue, const N: usize> LeanFnArgs for [T; N] {
+    unsafe fn apply(self, f: lean_obj_arg) -> lean_obj_res {
+        const { assert!(N > 0, "a closure must be applied to at least one argument") };
+        let mut args = self.map(T::into_boxed);
+        unsafe {
+            // `lean_apply_n()` only supports up to 16 arguments
+            if N <= 16 {
+                lean_apply_n(f, N as u32, args.as_mut_ptr())
+            } else {
+                lean_apply_m(f, N as u32, args.as_mut_ptr())
+            }
+        }
+    }
+}
+
+/// The type tag of Lean closures of type `Args → Ret`
+///
+/// `Args` is a tuple or an array of argument types. Lean closures support
+/// partial application and over-application, so `Args` need not match the
+/// arity of the underlying function: a closure of a Lean function of type
+/// `α → β → γ` can be used as a closure with arguments `(α, β)` and return type
+/// `γ`, or as a closure with arguments `(α,)` that returns a closure.
+pub struct ClosureTypeTag<Args, Ret>(PhantomData<fn(Args) -> Ret>);
+
+impl<Args, Ret> LeanTypeTag for ClosureTypeTag<Args, Ret> {
+    fn accepts_kind(kind: ObjectKind) -> bool {
+        kind == ObjectKind::Closure
+    }
+}
+
+pub type Closure<'r, Args, Ret> = Obj<'r, ClosureTypeTag<Args, Ret>>;
+pub type LeanFn<'r, Args, Ret> = Object<'r, ClosureTypeTag<Args, Ret>>;
+
+impl<Args, Ret> Obj<'_, ClosureTypeTag<Args, Ret>> {
+    /// The number of arguments of the underlying function, including fixed
+    /// arguments
+    pub fn arity(&self) -> u32 {
+        unsafe { lean_closure_arity(self.as_mut_raw()) }
+    }
+
+    /// The number of arguments that have already been applied
+    pub fn num_fixed(&self) -> u32 {
+        unsafe { lean_closure_num_fixed(self.as_mut_raw()) }
+    }
+}
+
+impl<Args: LeanFnArgs, Ret: LeanValue> Obj<'_, ClosureTypeTag<Args, Ret>> {
+    /// Applies the closure to all of its arguments, which are transferred to
+    /// the closure
+    pub fn call(&self, args: Args) -> Ret {
+        unsafe {
+            let f = self.as_mut_raw();
+            // The closure is consumed by `lean_apply_*()`
+            lean_inc(f);
+            Ret::from_boxed(args.apply(f))
+        }
+    }
+}
+
+impl<Args, Ret> Object<'_, ClosureTypeTag<Args, Ret>> {
+    pub fn arity(&self) -> u32 {
+        <Self as Borrow<Obj<_>>>::borrow(self).arity()
+    }
+
+    pub fn num_fixed(&self) -> u32 {
+        <Self as Borrow<Obj<_>>>::borrow(self).num_fixed()
+    }
+}
+
+impl<Args: LeanFnArgs, Ret: LeanValue> Object<'_, ClosureTypeTag<Args, Ret>> {
+    pub fn call(&self, args: Args) -> Ret {
+        <Self as Borrow<Obj<_>>>::borrow(self).call(args)
+    }
+}
+
+macro_rules! impl_partial_application {
+    ($first:ident, $($rest:ident),+) => {
+        impl<'r, $first: LeanValue, $($rest: LeanValue,)+ Ret: LeanValue>
+            Obj<'r, ClosureTypeTag<($first, $($rest,)+), Ret>>
+        {
+            /// Applies the closure to its first argument, returning a closure
+            /// of the remaining arguments
+            pub fn apply_first(
+                &self,
+                first: $first,
+            ) -> Object<'r, ClosureTypeTag<($($rest,)+), Ret>> {
+                unsafe {
+                    let f = self.as_mut_raw();
+                    lean_inc(f);
+                    Object::new(lean_apply_1(f, first.into_boxed()))
+                }
+            }
+        }
+
+        impl<'r, $first: LeanValue, $($rest: LeanValue,)+ Ret: LeanValue>
+            Object<'r, ClosureTypeTag<($first, $($rest,)+), Ret>>
+        {
+            pub fn apply_first(
+                &self,
+                first: $first,
+            ) -> Object<'r, ClosureTypeTag<($($rest,)+), Ret>> {
+                <Self as Borrow<Obj<_>>>::borrow(self).apply_first(first)
+            }
+        }
+    };
+}
+
+impl_partial_application!(A1, A2);
+impl_partial_application!(A1, A2, A3);
+impl_partial_application!(A1, A2, A3, A4);
+impl_partial_application!(A1, A2, A3, A4, A5);
+impl_partial_application!(A1, A2, A3, A4, A5, A6);
+impl_partial_application!(A1, A2, A3, A4, A5, A6, A7);
+impl_partial_application!(A1, A2, A3, A4, A5, A6, A7, A8);
+impl_partial_application!(A1, A2, A3, A4, A5, A6, A7, A8, A9);
+impl_partial_application!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10);
+impl_partial_application!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11);
+impl_partial_application!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12);
+impl_partial_application!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13);
+impl_partial_application!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14);
+impl_partial_application!(
+    A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15
+);
+impl_partial_application!(
+    A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15, A16
+);
+
+/// A Rust function that can be wrapped in a Lean closure of type `Args → Ret`
+///
+/// This trait is implemented by `Fn` closures that take the elements of the
+/// `Args` tuple as arguments. Such closures must be thread-safe because Lean
+/// code may share closures with other threads.
+pub trait RustFn<Args, Ret>: Send + Sync + 'static {
+    fn call_from_lean(&self, args: Args) -> Ret;
+}
+
+/// An adapter that implements [`RustFn`] for an `FnMut` closure, which is
+/// called with exclusive access to its state
+///
+/// Calling the closure again from within itself, for example through Lean
+/// code that calls back into Rust, deadlocks.
+pub struct RustFnMut<F>(Mutex<F>);
+
+impl<F> RustFnMut<F> {
+    pub fn new(f: F) -> Self {
+        Self(Mutex::new(f))
+    }
+}
+
+/// A tuple of arguments that a Lean closure can pass to a Rust function
+///
+/// # Safety
+///
+/// [`RustFnArgs::trampoline()`] must return a pointer to a function that takes
+/// the closure state followed by [`RustFnArgs::ARITY`] boxed arguments, all of
+/// them owned.
+pub unsafe trait RustFnArgs: Sized {
+    /// The number of arguments, excluding the closure state
+    const ARITY: u32;
+
+    /// A function pointer suitable for `lean_alloc_closure()` that calls `F`
+    fn trampoline<F: RustFn<Self, Ret>, Ret: LeanValue>() -> *mut c_void;
+}
+
+/// The type-erased Rust state of closures, which is stored in external objects
+type ClosureState = Box<dyn Any + Send + Sync>;
+
+unsafe extern "C" fn finalize_closure_state(data: *mut c_void) {
+    drop(unsafe { Box::from_raw(data.cast::<ClosureState>()) });
+}
+
+unsafe extern "C" fn foreach_closure_state(_data: *mut c_void, _f: lean_obj_arg) {}
+
+/// The external class of closure states, which all Rust closures share
+fn closure_state_class() -> *mut lean_external_class {
+    static CLASS: OnceLock<ExternalClass> = OnceLock::new();
+    CLASS
+        .get_or_init(|| unsafe {
+            ExternalClass(lean_register_external_class(
+                Some(finalize_closure_state),
+                Some(foreach_closure_state),
+            ))
+        })
+        .0
+}
+
+/// Calls Rust code from Lean, aborting the process through Lean's panic
+/// handler if the code panics, as unwinding into Lean code is undefined
+/// behavior
+///
+/// `what` describes the code in the panic message.
+pub(super) fn abort_on_panic<T>(what: &str, f: impl FnOnce() -> T) -> T {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(value) => value,
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("Box<dyn Any>");
+            let message = CString::new(format!("{what} panicked: {message}"))
+                .unwrap_or_else(|_| c"Rust code panicked".to_owned());
+            unsafe { lean_internal_panic(message.as_ptr()) }
+        }
+    }
+}
+
+/// Calls a Rust function from a trampoline
+///
+/// `args` converts the boxed arguments of the trampoline. Like the conversion
+/// of the return value, it runs under [`abort_on_panic()`], since conversions
+/// may panic as well.
+///
+/// # Safety
+///
+/// `state` must be an owned closure state of type `F`.
+unsafe fn call_rust_fn<F: RustFn<Args, Ret>, Args, Ret: LeanValue>(
+    state: lean_obj_arg,
+    args: impl FnOnce() -> Args,
+) -> lean_obj_res {
+    let ret = abort_on_panic("Rust closure", || {
+        let f = unsafe { &*lean_get_external_data(state).cast::<ClosureState>() };
+        let f = f
+            .downcast_ref::<F>()
+            .expect("closure state has an unexpected type");
+        f.call_from_lean(args()).into_boxed()
+    });
+    unsafe { lean_dec(state) };
+    ret
+}
+
+macro_rules! impl_rust_fn {
+    ($trampoline:ident; $($arg:ident),+) => {
+        impl<F, $($arg,)+ Ret> RustFn<($($arg,)+), Ret> for F
+        where
+            F: Fn($($arg),+) -> Ret + Send + Sync + 'static,
+        {
+            fn call_from_lean(&self, args: ($($arg,)+)) -> Ret {
+                #[allow(non_snake_case)]
+                let ($($arg,)+) = args;
+                self($($arg),+)
+            }
+        }
+
+        impl<F, $($arg,)+ Ret> RustFn<($($arg,)+), Ret> for RustFnMut<F>
+        where
+            F: FnMut($($arg),+) -> Ret + Send + 'static,
+        {
+            fn call_from_lean(&self, args: ($($arg,)+)) -> Ret {
+                #[allow(non_snake_case)]
+                let ($($arg,)+) = args;
+                let mut f = self.0.lock().unwrap_or_else(|error| error.into_inner());
+                f($($arg),+)
+            }
+        }
+
+        #[allow(non_snake_case)]
+        unsafe extern "C" fn $trampoline<F: RustFn<($($arg,)+), Ret>, $($arg: LeanValue,)+ Ret: LeanValue>(
+            state: lean_obj_arg,
+            $($arg: lean_obj_arg),+
+        ) -> lean_obj_res {
+            unsafe {
+                call_rust_fn::<F, _, Ret>(state, || ($($arg::from_boxed($arg),)+))
+            }
+        }
+
+        unsafe impl<$($arg: LeanValue),+> RustFnArgs for ($($arg,)+) {
+            const ARITY: u32 = [$(stringify!($arg)),+].len() as u32;
+
+            fn trampoline<F: RustFn<Self, Ret>, Ret: LeanValue>() -> *mut c_void {
+                $trampoline::<F, $($arg,)+ Ret> as *mut c_void
+            }
+        }
+    };
+}
+
+// Closures take their state as an additional argument, and Lean closures of
+// functions with more than 16 arguments use a different calling convention
+impl_rust_fn!(trampoline_1; A1);
+impl_rust_fn!(trampoline_2; A1, A2);
+impl_rust_fn!(trampoline_3; A1, A2, A3);
+impl_rust_fn!(trampoline_4; A1, A2, A3, A4);
+impl_rust_fn!(trampoline_5; A1, A2, A3, A4, A5);
+impl_rust_fn!(trampoline_6; A1, A2, A3, A4, A5, A6);
+impl_rust_fn!(trampoline_7; A1, A2, A3, A4, A5, A6, A7);
+impl_rust_fn!(trampoline_8; A1, A2, A3, A4, A5, A6, A7, A8);
+impl_rust_fn!(trampoline_9; A1, A2, A3, A4, A5, A6, A7, A8, A9);
+impl_rust_fn!(trampoline_10; A1, A2, A3, A4, A5, A6, A7, A8, A9, A10);
+impl_rust_fn!(trampoline_11; A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11);
+impl_rust_fn!(trampoline_12; A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12);
+impl_rust_fn!(trampoline_13; A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13);
+impl_rust_fn!(trampoline_14; A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14);
+impl_rust_fn!(trampoline_15; A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15);
+
+/// Allocates a Lean closure that calls `f`, for internal closures that are not
+/// exposed as typed objects
+pub(crate) fn alloc_rust_closure<Args: RustFnArgs, Ret: LeanValue, F: RustFn<Args, Ret>>(
+    f: F,
+) -> lean_obj_res {
+    let state: *mut ClosureState = Box::into_raw(Box::new(Box::new(f)));
+    unsafe {
+        let state = lean_alloc_external(closure_state_class(), state.cast());
+        let closure = lean_alloc_closure(Args::trampoline::<F, Ret>(), Args::ARITY + 1, 1);
+        lean_closure_set(closure, 0, state);
+        closure
+    }
+}
+
+impl<'r, Args: RustFnArgs, Ret: LeanValue> Object<'r, ClosureTypeTag<Args, Ret>> {
+    /// Wraps a Rust function in a Lean closure
+    ///
+    /// The function is dropped when Lean frees the closure. If the function
+    /// panics, the panic is caught and reported through Lean's panic handler,
+    /// which terminates the process.
+    pub fn from_fn<R: Minimal, M: Modules, F: RustFn<Args, Ret>>(
+        _runtime: &'r Runtime<R, M>,
+        f: F,
+    ) -> Self {
+        unsafe { Self::new(alloc_rust_closure(f)) }
+    }
+
+    /// Wraps a Rust `FnMut` closure in a Lean closure
+    ///
+    /// See [`RustFnMut`] and [`Object::from_fn()`].
+    pub fn from_fn_mut<R: Minimal, M: Modules, F>(runtime: &'r Runtime<R, M>, f: F) -> Self
+    where
+        RustFnMut<F>: RustFn<Args, Ret>,
+    {
+        Self::from_fn(runtime, RustFnMut::new(f))
+    }
+}
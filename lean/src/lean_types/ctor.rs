@@ -0,0 +1,746 @@
+use std::borrow::Borrow;
+use std::marker::PhantomData;
+use std::mem;
+
+use lean_sys::{
+    b_lean_obj_arg, lean_alloc_ctor, lean_box, lean_ctor_get, lean_ctor_get_float,
+    lean_ctor_get_float32, lean_ctor_get_uint8, lean_ctor_get_uint16, lean_ctor_get_uint32,
+    lean_ctor_get_uint64, lean_ctor_get_usize, lean_ctor_num_objs, lean_ctor_set,
+    lean_ctor_set_float, lean_ctor_set_float32, lean_ctor_set_uint8, lean_ctor_set_uint16,
+    lean_ctor_set_uint32, lean_ctor_set_uint64, lean_ctor_set_usize, lean_dec, lean_is_exclusive,
+    lean_is_scalar, lean_obj_tag, lean_object,
+};
+
+use super::{
+    Owner, Reference,
+    any::{LeanTypeTag, ObjectKind},
+    object::{Obj, Object},
+};
+use crate::{Minimal, Modules, Runtime};
+
+/// The runtime layout of a Lean constructor
+///
+/// Lean stores the fields of a constructor object in the following order:
+/// boxed fields (pointers to objects or boxed scalars), `USize` fields, and
+/// then other unboxed scalar fields. See [Lean's FFI
+/// documentation](https://github.com/leanprover/lean4/blob/master/doc/dev/ffi.md#inductive-types)
+/// for more information.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CtorLayout {
+    /// The number of boxed fields
+    pub num_objs: u32,
+    /// The number of `USize` fields
+    pub num_usizes: u32,
+    /// The total size, in bytes, of the remaining unboxed scalar fields
+    pub scalar_size: u32,
+}
+
+impl CtorLayout {
+    pub const fn new(num_objs: u32, num_usizes: u32, scalar_size: u32) -> Self {
+        Self {
+            num_objs,
+            num_usizes,
+            scalar_size,
+        }
+    }
+
+    /// The offset, in bytes, of the scalar area from the start of the object
+    /// fields, as expected by functions such as `lean_ctor_get_uint32()`
+    const fn scalar_area_offset(&self) -> u32 {
+        (self.num_objs + self.num_usizes) * mem::size_of::<usize>() as u32
+    }
+
+    /// The number of bytes to allocate for the `USize` and scalar fields, as
+    /// expected by `lean_alloc_ctor()`
+    pub const fn allocation_scalar_size(&self) -> u32 {
+        self.num_usizes * mem::size_of::<usize>() as u32 + self.scalar_size
+    }
+
+    /// Computes the layout of a constructor whose relevant fields have the
+    /// given kinds
+    pub const fn from_fields(fields: &[FieldKind]) -> Self {
+        let mut layout = Self::new(0, 0, 0);
+        let mut i = 0;
+        while i < fields.len() {
+            match fields[i] {
+                FieldKind::Object => layout.num_objs += 1,
+                FieldKind::Usize => layout.num_usizes += 1,
+                FieldKind::Scalar(size) => layout.scalar_size += size,
+            }
+            i += 1;
+        }
+        layout
+    }
+
+    /// Computes the position of the field at `index` among the fields of the
+    /// same kind, in the order used by the Lean compiler
+    ///
+    /// `fields` lists the kinds of the relevant fields of a constructor in
+    /// declaration order. The position of a boxed or `USize` field is its index
+    /// among the fields of its kind, as expected by [`ObjectField::new()`] and
+    /// [`UsizeField::new()`]. The position of a scalar field is its offset in
+    /// the scalar area, as expected by [`ScalarField::new()`]: scalar fields
+    /// are sorted by decreasing size, and fields of the same size keep their
+    /// declaration order.
+    pub const fn field_position(fields: &[FieldKind], index: usize) -> u32 {
+        let kind = fields[index];
+        let mut position = 0;
+        let mut i = 0;
+        while i < fields.len() {
+            match (kind, fields[i]) {
+                (FieldKind::Object, FieldKind::Object) | (FieldKind::Usize, FieldKind::Usize)
+                    if i < index =>
+                {
+                    position += 1
+                }
+                (FieldKind::Scalar(size), FieldKind::Scalar(other_size))
+                    if other_size > size || (other_size == size && i < index) =>
+                {
+                    position += other_size
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        position
+    }
+
+    /// Converts the position of a field, as computed by
+    /// [`CtorLayout::field_position()`], to the index or offset expected by
+    /// the `lean_ctor_get*()` and `lean_ctor_set*()` functions
+    pub const fn field_location(&self, kind: FieldKind, position: u32) -> u32 {
+        match kind {
+            FieldKind::Object => position,
+            FieldKind::Usize => self.num_objs + position,
+            FieldKind::Scalar(_) => self.scalar_area_offset() + position,
+        }
+    }
+}
+
+/// How a relevant field of a constructor is stored
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldKind {
+    /// A boxed field
+    Object,
+    /// A `USize` field
+    Usize,
+    /// An unboxed scalar field of the given size, in bytes
+    Scalar(u32),
+}
+
+/// A trait implemented by type tags of Lean structures and inductive types that
+/// are represented as constructor objects
+///
+/// # Safety
+///
+/// Implementations of this trait must describe the layouts that the Lean
+/// compiler generates for the type's constructors.
+pub unsafe trait LeanCtorTypeTag {
+    /// The layouts of the type's constructors, indexed by constructor tag
+    const LAYOUTS: &'static [CtorLayout];
+}
+
+/// Constructor objects are accepted if their tag and number of boxed fields
+/// match a layout, and boxed scalars if they encode the tag of a constructor
+/// without fields
+impl<TypeTag: LeanCtorTypeTag> LeanTypeTag for TypeTag {
+    fn accepts_kind(kind: ObjectKind) -> bool {
+        match kind {
+            ObjectKind::Ctor { tag, num_objs } => TypeTag::LAYOUTS
+                .get(tag as usize)
+                .is_some_and(|layout| layout.num_objs == num_objs),
+            ObjectKind::Scalar(tag) => TypeTag::LAYOUTS
+                .get(tag)
+                .is_some_and(|layout| *layout == CtorLayout::new(0, 0, 0)),
+            _ => false,
+        }
+    }
+}
+
+/// An unboxed scalar type that can be stored in a constructor's scalar area
+///
+/// # Safety
+///
+/// Implementations must read and write exactly `size_of::<Self>()` bytes at
+/// the given offset.
+pub unsafe trait CtorScalar: Copy {
+    /// # Safety
+    ///
+    /// `ctor` must be a constructor object with a field of this type at
+    /// `offset`, where `offset` is relative to the start of the object fields.
+    unsafe fn get(ctor: b_lean_obj_arg, offset: u32) -> Self;
+
+    /// # Safety
+    ///
+    /// `ctor` must be an exclusively-owned constructor object with a field of
+    /// this type at `offset`, where `offset` is relative to the start of the
+    /// object fields.
+    unsafe fn set(ctor: b_lean_obj_arg, offset: u32, value: Self);
+}
+
+macro_rules! impl_ctor_scalar {
+    ($type:ty, $lean_type:ty, $lean_get:ident, $lean_set:ident) => {
+        unsafe impl CtorScalar for $type {
+            unsafe fn get(ctor: b_lean_obj_arg, offset: u32) -> Self {
+                unsafe { $lean_get(ctor, offset) as $type }
+            }
+
+            unsafe fn set(ctor: b_lean_obj_arg, offset: u32, value: Self) {
+                unsafe { $lean_set(ctor, offset, value as $lean_type) }
+            }
+        }
+    };
+}
+
+impl_ctor_scalar!(u8, u8, lean_ctor_get_uint8, lean_ctor_set_uint8);
+impl_ctor_scalar!(i8, u8, lean_ctor_get_uint8, lean_ctor_set_uint8);
+impl_ctor_scalar!(u16, u16, lean_ctor_get_uint16, lean_ctor_set_uint16);
+impl_ctor_scalar!(i16, u16, lean_ctor_get_uint16, lean_ctor_set_uint16);
+impl_ctor_scalar!(u32, u32, lean_ctor_get_uint32, lean_ctor_set_uint32);
+impl_ctor_scalar!(i32, u32, lean_ctor_get_uint32, lean_ctor_set_uint32);
+impl_ctor_scalar!(u64, u64, lean_ctor_get_uint64, lean_ctor_set_uint64);
+impl_ctor_scalar!(i64, u64, lean_ctor_get_uint64, lean_ctor_set_uint64);
+impl_ctor_scalar!(f32, f32, lean_ctor_get_float32, lean_ctor_set_float32);
+impl_ctor_scalar!(f64, f64, lean_ctor_get_float, lean_ctor_set_float);
+
+unsafe impl CtorScalar for bool {
+    unsafe fn get(ctor: b_lean_obj_arg, offset: u32) -> Self {
+        unsafe { lean_ctor_get_uint8(ctor, offset) != 0 }
+    }
+
+    unsafe fn set(ctor: b_lean_obj_arg, offset: u32, value: Self) {
+        unsafe { lean_ctor_set_uint8(ctor, offset, value.into()) }
+    }
+}
+
+unsafe impl CtorScalar for char {
+    unsafe fn get(ctor: b_lean_obj_arg, offset: u32) -> Self {
+        // Lean's `Char` is guaranteed to be a valid Unicode scalar value
+        unsafe { char::from_u32_unchecked(lean_ctor_get_uint32(ctor, offset)) }
+    }
+
+    unsafe fn set(ctor: b_lean_obj_arg, offset: u32, value: Self) {
+        unsafe { lean_ctor_set_uint32(ctor, offset, value.into()) }
+    }
+}
+
+/// A boxed field of a constructor, identified by its index among the boxed
+/// fields
+pub struct ObjectField<T> {
+    index: u32,
+    type_tag: PhantomData<T>,
+}
+
+impl<T> ObjectField<T> {
+    /// # Safety
+    ///
+    /// Callers must ensure that the field at `index` has type `T`.
+    pub const unsafe fn new(index: u32) -> Self {
+        Self {
+            index,
+            type_tag: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for ObjectField<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ObjectField<T> {}
+
+/// A `USize` field of a constructor, identified by its index among the `USize`
+/// fields
+#[derive(Clone, Copy)]
+pub struct UsizeField {
+    index: u32,
+}
+
+impl UsizeField {
+    /// # Safety
+    ///
+    /// Callers must ensure that the constructor has a `USize` field at `index`.
+    pub const unsafe fn new(index: u32) -> Self {
+        Self { index }
+    }
+}
+
+/// An unboxed scalar field of a constructor, identified by its offset, in
+/// bytes, from the start of the scalar fields that follow the `USize` fields
+pub struct ScalarField<T: CtorScalar> {
+    offset: u32,
+    scalar_type: PhantomData<T>,
+}
+
+impl<T: CtorScalar> ScalarField<T> {
+    /// # Safety
+    ///
+    /// Callers must ensure that the field at `offset` has type `T`.
+    pub const unsafe fn new(offset: u32) -> Self {
+        Self {
+            offset,
+            scalar_type: PhantomData,
+        }
+    }
+}
+
+impl<T: CtorScalar> Clone for ScalarField<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: CtorScalar> Copy for ScalarField<T> {}
+
+fn layout_of<TypeTag: LeanCtorTypeTag>(ctor: *mut lean_object) -> CtorLayout {
+    let tag = unsafe { lean_obj_tag(ctor) } as usize;
+    let layout = *TypeTag::LAYOUTS.get(tag).unwrap_or_else(|| {
+        panic!(
+            "constructor tag {tag} is out of bounds for a type with {} constructors",
+            TypeTag::LAYOUTS.len()
+        )
+    });
+    debug_assert_eq!(
+        unsafe { lean_ctor_num_objs(ctor) },
+        layout.num_objs,
+        "constructor object has an unexpected number of boxed fields"
+    );
+    layout
+}
+
+fn check_object_field(layout: &CtorLayout, index: u32) {
+    debug_assert!(
+        index < layout.num_objs,
+        "boxed field index {index} is out of bounds for a constructor with {} boxed fields",
+        layout.num_objs
+    );
+}
+
+fn check_usize_field(layout: &CtorLayout, index: u32) {
+    debug_assert!(
+        index < layout.num_usizes,
+        "`USize` field index {index} is out of bounds for a constructor with {} `USize` fields",
+        layout.num_usizes
+    );
+}
+
+fn check_scalar_field<T: CtorScalar>(layout: &CtorLayout, offset: u32) -> u32 {
+    debug_assert!(
+        offset as usize + mem::size_of::<T>() <= layout.scalar_size as usize,
+        "scalar field of {} bytes at offset {offset} is out of bounds for a scalar area of {} bytes",
+        mem::size_of::<T>(),
+        layout.scalar_size
+    );
+    layout.scalar_area_offset() + offset
+}
+
+/// A read-only view of a constructor object
+pub struct Ctor<'a, 'r, TypeTag: LeanCtorTypeTag> {
+    object: &'a Obj<'r, TypeTag>,
+    layout: CtorLayout,
+}
+
+impl<'a, 'r, TypeTag: LeanCtorTypeTag> Ctor<'a, 'r, TypeTag> {
+    pub fn tag(&self) -> u32 {
+        unsafe { lean_obj_tag(self.object.as_mut_raw()) }
+    }
+
+    pub fn layout(&self) -> CtorLayout {
+        self.layout
+    }
+
+    pub fn object<T: LeanTypeTag>(&self, field: ObjectField<T>) -> &'a Obj<'r, T> {
+        check_object_field(&self.layout, field.index);
+        unsafe { <Object<'r, T> as LeanField<'r>>::read_ref(self.object.as_mut_raw(), field.index) }
+    }
+
+    pub fn usize(&self, field: UsizeField) -> usize {
+        check_usize_field(&self.layout, field.index);
+        unsafe { lean_ctor_get_usize(self.object.as_mut_raw(), self.layout.num_objs + field.index) }
+    }
+
+    pub fn scalar<T: CtorScalar>(&self, field: ScalarField<T>) -> T {
+        let offset = check_scalar_field::<T>(&self.layout, field.offset);
+        unsafe { T::get(self.object.as_mut_raw(), offset) }
+    }
+}
+
+/// A mutable view of an exclusively-owned constructor object
+pub struct CtorMut<'a, 'r, TypeTag: LeanCtorTypeTag> {
+    object: &'a mut Object<'r, TypeTag>,
+    layout: CtorLayout,
+}
+
+impl<'r, TypeTag: LeanCtorTypeTag> CtorMut<'_, 'r, TypeTag> {
+    pub fn as_ctor(&self) -> Ctor<'_, 'r, TypeTag> {
+        Ctor {
+            object: (*self.object).borrow(),
+            layout: self.layout,
+        }
+    }
+
+    pub fn set_object<T: LeanTypeTag>(&mut self, field: ObjectField<T>, value: Object<'r, T>) {
+        check_object_field(&self.layout, field.index);
+        unsafe {
+            let ctor = self.object.as_mut_raw();
+            lean_dec(lean_ctor_get(ctor, field.index));
+            lean_ctor_set(ctor, field.index, value.into_raw());
+        }
+    }
+
+    pub fn set_usize(&mut self, field: UsizeField, value: usize) {
+        check_usize_field(&self.layout, field.index);
+        unsafe {
+            lean_ctor_set_usize(
+                self.object.as_mut_raw(),
+                self.layout.num_objs + field.index,
+                value,
+            )
+        }
+    }
+
+    pub fn set_scalar<T: CtorScalar>(&mut self, field: ScalarField<T>, value: T) {
+        let offset = check_scalar_field::<T>(&self.layout, field.offset);
+        unsafe { T::set(self.object.as_mut_raw(), offset, value) }
+    }
+}
+
+/// A builder for a new constructor object
+///
+/// All boxed fields must be set before calling [`CtorBuilder::build()`].
+pub struct CtorBuilder<'r, TypeTag: LeanCtorTypeTag> {
+    object: Object<'r, TypeTag>,
+    layout: CtorLayout,
+    initialized_objects: Vec<bool>,
+}
+
+impl<'r, TypeTag: LeanCtorTypeTag> CtorBuilder<'r, TypeTag> {
+    pub fn new<R: Minimal, M: Modules>(_runtime: &'r Runtime<R, M>, tag: u32) -> Self {
+        let layout = *TypeTag::LAYOUTS.get(tag as usize).unwrap_or_else(|| {
+            panic!(
+                "constructor tag {tag} is out of bounds for a type with {} constructors",
+                TypeTag::LAYOUTS.len()
+            )
+        });
+        let object = unsafe {
+            let ctor = lean_alloc_ctor(tag, layout.num_objs, layout.allocation_scalar_size());
+            // Make the object safe to drop before all fields are initialized
+            for i in 0..layout.num_objs {
+                lean_ctor_set(ctor, i, lean_box(0));
+            }
+            Object::new(ctor)
+        };
+        Self {
+            object,
+            layout,
+            initialized_objects: vec![false; layout.num_objs as usize],
+        }
+    }
+
+    fn as_ctor_mut(&mut self) -> CtorMut<'_, 'r, TypeTag> {
+        CtorMut {
+            object: &mut self.object,
+            layout: self.layout,
+        }
+    }
+
+    pub fn set_object<T: LeanTypeTag>(
+        &mut self,
+        field: ObjectField<T>,
+        value: Object<'r, T>,
+    ) -> &mut Self {
+        self.as_ctor_mut().set_object(field, value);
+        self.initialized_objects[field.index as usize] = true;
+        self
+    }
+
+    pub fn set_usize(&mut self, field: UsizeField, value: usize) -> &mut Self {
+        self.as_ctor_mut().set_usize(field, value);
+        self
+    }
+
+    pub fn set_scalar<T: CtorScalar>(&mut self, field: ScalarField<T>, value: T) -> &mut Self {
+        self.as_ctor_mut().set_scalar(field, value);
+        self
+    }
+
+    /// # Panics
+    ///
+    /// Panics if any boxed field has not been set.
+    pub fn build(self) -> Object<'r, TypeTag> {
+        if let Some(index) = self.initialized_objects.iter().position(|set| !set) {
+            panic!("boxed field {index} of the constructor object was not set");
+        }
+        self.object
+    }
+}
+
+impl<'r, TypeTag: LeanCtorTypeTag> Obj<'r, TypeTag> {
+    /// The constructor tag of this object
+    ///
+    /// Constructors without fields may be represented as scalars, in which case
+    /// the scalar value is the constructor tag.
+    pub fn ctor_tag(&self) -> u32 {
+        unsafe { lean_obj_tag(self.as_mut_raw()) }
+    }
+
+    /// View this object as a constructor object, unless it is a scalar
+    pub fn as_ctor(&self) -> Option<Ctor<'_, 'r, TypeTag>> {
+        let ctor = unsafe { self.as_mut_raw() };
+        if unsafe { lean_is_scalar(ctor) } {
+            None
+        } else {
+            Some(Ctor {
+                object: self,
+                layout: layout_of::<TypeTag>(ctor),
+            })
+        }
+    }
+}
+
+impl<'r, TypeTag: LeanCtorTypeTag> Object<'r, TypeTag> {
+    pub fn ctor_tag(&self) -> u32 {
+        <Self as Borrow<Obj<_>>>::borrow(self).ctor_tag()
+    }
+
+    pub fn as_ctor(&self) -> Option<Ctor<'_, 'r, TypeTag>> {
+        <Self as Borrow<Obj<_>>>::borrow(self).as_ctor()
+    }
+
+    /// Mutably view this object as a constructor object, unless it is a
+    /// scalar or it is shared with other owners
+    pub fn as_ctor_mut(&mut self) -> Option<CtorMut<'_, 'r, TypeTag>> {
+        let ctor = unsafe { self.as_mut_raw() };
+        if unsafe { lean_is_scalar(ctor) || !lean_is_exclusive(ctor) } {
+            None
+        } else {
+            Some(CtorMut {
+                layout: layout_of::<TypeTag>(ctor),
+                object: self,
+            })
+        }
+    }
+}
+
+/// A Rust type that can be stored in a relevant field of a constructor object
+///
+/// `'r` is the lifetime of the runtime that the constructor object belongs to,
+/// which boxed fields inherit.
+///
+/// # Safety
+///
+/// Implementations must read and write fields of the kind described by
+/// [`LeanField::KIND`], and scalar fields must be exactly as large as their
+/// size in [`FieldKind::Scalar`].
+pub unsafe trait LeanField<'r>: Sized {
+    const KIND: FieldKind;
+
+    /// The type of a field that is read without sharing it, which borrows
+    /// boxed fields from the constructor object
+    type Ref<'a>
+    where
+        Self: 'a,
+        'r: 'a;
+
+    /// Reads a field without consuming the constructor object
+    ///
+    /// # Safety
+    ///
+    /// `ctor` must be a constructor object that belongs to a runtime that
+    /// outlives `'r`, with a field of this type at `location`, as computed by
+    /// [`CtorLayout::field_location()`].
+    unsafe fn read(ctor: b_lean_obj_arg, location: u32) -> Self;
+
+    /// Reads a field, borrowing it if it is boxed
+    ///
+    /// # Safety
+    ///
+    /// In addition to the requirements of [`LeanField::read()`], `ctor` must
+    /// outlive `'a`.
+    unsafe fn read_ref<'a>(ctor: b_lean_obj_arg, location: u32) -> Self::Ref<'a>
+    where
+        Self: 'a,
+        'r: 'a;
+
+    /// Initializes a field of a newly allocated constructor object
+    ///
+    /// # Safety
+    ///
+    /// `ctor` must be an exclusively-owned constructor object with an
+    /// uninitialized field of this type at `location`, as computed by
+    /// [`CtorLayout::field_location()`].
+    unsafe fn write(self, ctor: b_lean_obj_arg, location: u32);
+}
+
+macro_rules! impl_lean_field_for_scalar {
+    ($($type:ty),*) => {
+        $(
+            unsafe impl<'r> LeanField<'r> for $type {
+                const KIND: FieldKind = FieldKind::Scalar(mem::size_of::<$type>() as u32);
+
+                type Ref<'a>
+                    = Self
+                where
+                    'r: 'a;
+
+                unsafe fn read(ctor: b_lean_obj_arg, location: u32) -> Self {
+                    unsafe { <$type as CtorScalar>::get(ctor, location) }
+                }
+
+                unsafe fn read_ref<'a>(ctor: b_lean_obj_arg, location: u32) -> Self::Ref<'a>
+                where
+                    'r: 'a,
+                {
+                    unsafe { Self::read(ctor, location) }
+                }
+
+                unsafe fn write(self, ctor: b_lean_obj_arg, location: u32) {
+                    unsafe { <$type as CtorScalar>::set(ctor, location, self) }
+                }
+            }
+        )*
+    };
+}
+
+impl_lean_field_for_scalar!(u8, i8, u16, i16, u32, i32, u64, i64, f32, f64, bool, char);
+
+unsafe impl<'r> LeanField<'r> for usize {
+    const KIND: FieldKind = FieldKind::Usize;
+
+    type Ref<'a>
+        = Self
+    where
+        'r: 'a;
+
+    unsafe fn read(ctor: b_lean_obj_arg, location: u32) -> Self {
+        unsafe { lean_ctor_get_usize(ctor, location) }
+    }
+
+    unsafe fn read_ref<'a>(ctor: b_lean_obj_arg, location: u32) -> Self::Ref<'a>
+    where
+        'r: 'a,
+    {
+        unsafe { Self::read(ctor, location) }
+    }
+
+    unsafe fn write(self, ctor: b_lean_obj_arg, location: u32) {
+        unsafe { lean_ctor_set_usize(ctor, location, self) }
+    }
+}
+
+unsafe impl<'r, T: LeanTypeTag> LeanField<'r> for Object<'r, T> {
+    const KIND: FieldKind = FieldKind::Object;
+
+    type Ref<'a>
+        = &'a Obj<'r, T>
+    where
+        Self: 'a,
+        'r: 'a;
+
+    unsafe fn read(ctor: b_lean_obj_arg, location: u32) -> Self {
+        unsafe { Self::read_ref(ctor, location) }.to_owned()
+    }
+
+    unsafe fn read_ref<'a>(ctor: b_lean_obj_arg, location: u32) -> &'a Obj<'r, T>
+    where
+        Self: 'a,
+        'r: 'a,
+    {
+        unsafe { Obj::from_raw(lean_ctor_get(ctor, location)) }
+    }
+
+    unsafe fn write(self, ctor: b_lean_obj_arg, location: u32) {
+        unsafe { lean_ctor_set(ctor, location, self.into_raw()) }
+    }
+}
+
+unsafe impl<'r, T: LeanStructure<'r>> LeanField<'r> for T {
+    const KIND: FieldKind = FieldKind::Object;
+
+    type Ref<'a>
+        = &'a Obj<'r, T>
+    where
+        Self: 'a,
+        'r: 'a;
+
+    unsafe fn read(ctor: b_lean_obj_arg, location: u32) -> Self {
+        T::from_lean(unsafe { Self::read_ref(ctor, location) })
+    }
+
+    unsafe fn read_ref<'a>(ctor: b_lean_obj_arg, location: u32) -> &'a Obj<'r, T>
+    where
+        Self: 'a,
+        'r: 'a,
+    {
+        unsafe { <Object<'r, T> as LeanField<'r>>::read_ref(ctor, location) }
+    }
+
+    unsafe fn write(self, ctor: b_lean_obj_arg, location: u32) {
+        unsafe { self.into_lean_unchecked().write(ctor, location) }
+    }
+}
+
+/// A Rust struct whose fields mirror the relevant fields of a Lean structure
+///
+/// The struct itself is the type tag of the corresponding Lean objects. This
+/// trait is usually implemented with `#[derive(LeanStructure)]`, which computes
+/// the constructor layout from the types of the fields. Structs with boxed
+/// fields have a lifetime parameter for the runtime, `'r`, which is passed to
+/// their fields.
+///
+/// # Safety
+///
+/// Implementations must read and write the fields at the positions chosen by
+/// the Lean compiler, as described by [`LeanCtorTypeTag::LAYOUTS`].
+pub unsafe trait LeanStructure<'r>: LeanCtorTypeTag + Sized {
+    /// Copies the fields of a Lean structure, sharing its boxed fields
+    fn from_lean(object: &Obj<'r, Self>) -> Self;
+
+    /// # Safety
+    ///
+    /// Callers must ensure that the Lean runtime is initialized and outlives
+    /// `'r`.
+    unsafe fn into_lean_unchecked(self) -> Object<'r, Self>;
+
+    fn into_lean<R: Minimal, M: Modules>(self, _runtime: &'r Runtime<R, M>) -> Object<'r, Self> {
+        unsafe { self.into_lean_unchecked() }
+    }
+}
+
+/// A Rust enum whose variants mirror the constructors of a Lean inductive type
+///
+/// The enum itself is the type tag of the corresponding Lean objects. Variants
+/// are matched with constructors in declaration order. Constructors without
+/// relevant fields are represented as boxed scalars and the others as
+/// constructor objects. This trait is usually implemented with
+/// `#[derive(LeanInductive)]`.
+///
+/// # Safety
+///
+/// Implementations must read and write the fields at the positions chosen by
+/// the Lean compiler, as described by [`LeanCtorTypeTag::LAYOUTS`].
+pub unsafe trait LeanInductive<'r>: LeanCtorTypeTag + Sized {
+    /// A view of a Lean object for pattern matching, which borrows the boxed
+    /// fields of its constructor instead of sharing them
+    type View<'a>
+    where
+        Self: 'a,
+        'r: 'a;
+
+    /// Copies the fields of a Lean object, sharing its boxed fields
+    fn from_lean(object: &Obj<'r, Self>) -> Self;
+
+    fn view<'a>(object: &'a Obj<'r, Self>) -> Self::View<'a>;
+
+    /// # Safety
+    ///
+    /// Callers must ensure that the Lean runtime is initialized and outlives
+    /// `'r`.
+    unsafe fn into_lean_unchecked(self) -> Object<'r, Self>;
+
+    fn into_lean<R: Minimal, M: Modules>(self, _runtime: &'r Runtime<R, M>) -> Object<'r, Self> {
+        unsafe { self.into_lean_unchecked() }
+    }
+}
@@ -0,0 +1,151 @@
+use std::borrow::Borrow;
+use std::marker::PhantomData;
+
+use lean_sys::{
+    b_lean_obj_arg, lean_alloc_ctor, lean_ctor_get, lean_ctor_set, lean_dec, lean_inc,
+    lean_obj_arg, lean_obj_tag, lean_object,
+};
+
+use super::{
+    Owner, Reference,
+    any::{AnyObj, CheckedTypeTag, LeanTypeTag, ObjectKind},
+    array::{CheckedArrayTypeTag, LeanArrayTypeTag},
+    object::{Obj, Object},
+};
+use crate::{Minimal, Modules, Runtime};
+
+/// The type tag of Lean's `Except ε α`, where `E` and `A` are the type tags of
+/// `ε` and `α`, respectively
+///
+/// Both constructors are represented as constructor objects with one field.
+/// `Except` can itself be used as an element type.
+pub struct ExceptTypeTag<E: LeanArrayTypeTag, A: LeanArrayTypeTag>(PhantomData<(E, A)>);
+
+impl<E: LeanArrayTypeTag, A: LeanArrayTypeTag> ExceptTypeTag<E, A> {
+    const ERROR_TAG: u32 = 0;
+    const OK_TAG: u32 = 1;
+}
+
+unsafe impl<E: LeanArrayTypeTag, A: LeanArrayTypeTag> LeanArrayTypeTag for ExceptTypeTag<E, A> {
+    type Input<'r> = Result<A::Input<'r>, E::Input<'r>>;
+    type Output<'a, 'r>
+        = Result<A::Output<'a, 'r>, E::Output<'a, 'r>>
+    where
+        Self: 'a,
+        'r: 'a;
+
+    fn into_element(input: Self::Input<'_>) -> *mut lean_object {
+        let (tag, value) = match input {
+            Ok(value) => (Self::OK_TAG, A::into_element(value)),
+            Err(error) => (Self::ERROR_TAG, E::into_element(error)),
+        };
+        unsafe {
+            let except = lean_alloc_ctor(tag, 1, 0);
+            lean_ctor_set(except, 0, value);
+            except
+        }
+    }
+
+    unsafe fn from_element<'a, 'r>(element: b_lean_obj_arg) -> Self::Output<'a, 'r>
+    where
+        Self: 'a,
+        'r: 'a,
+    {
+        unsafe {
+            let value = lean_ctor_get(element, 0);
+            if lean_obj_tag(element) == Self::OK_TAG {
+                Ok(A::from_element(value))
+            } else {
+                Err(E::from_element(value))
+            }
+        }
+    }
+
+    unsafe fn take_element<'r>(element: lean_obj_arg) -> Self::Input<'r> {
+        unsafe {
+            let value = lean_ctor_get(element, 0);
+            let tag = lean_obj_tag(element);
+            lean_inc(value);
+            lean_dec(element);
+            if tag == Self::OK_TAG {
+                Ok(A::take_element(value))
+            } else {
+                Err(E::take_element(value))
+            }
+        }
+    }
+}
+
+impl<E: LeanArrayTypeTag, A: LeanArrayTypeTag> LeanTypeTag for ExceptTypeTag<E, A> {
+    fn accepts_kind(kind: ObjectKind) -> bool {
+        matches!(
+            kind,
+            ObjectKind::Ctor { tag, num_objs: 1 } if tag == Self::ERROR_TAG || tag == Self::OK_TAG
+        )
+    }
+}
+
+unsafe impl<E: CheckedArrayTypeTag, A: CheckedArrayTypeTag> CheckedArrayTypeTag
+    for ExceptTypeTag<E, A>
+{
+    fn matches_element(element: &AnyObj<'_>) -> bool {
+        let matches_value = match element.object_kind() {
+            ObjectKind::Ctor { tag, num_objs: 1 } if tag == Self::ERROR_TAG => E::matches_element,
+            ObjectKind::Ctor { tag, num_objs: 1 } if tag == Self::OK_TAG => A::matches_element,
+            _ => return false,
+        };
+        matches_value(unsafe { element.ctor_field(0) })
+    }
+}
+
+unsafe impl<E: CheckedArrayTypeTag, A: CheckedArrayTypeTag> CheckedTypeTag for ExceptTypeTag<E, A> {
+    fn matches(object: &AnyObj<'_>) -> bool {
+        Self::matches_element(object)
+    }
+}
+
+impl<'r, E: LeanArrayTypeTag, A: LeanArrayTypeTag> Obj<'r, ExceptTypeTag<E, A>> {
+    pub fn is_ok(&self) -> bool {
+        unsafe { lean_obj_tag(self.as_mut_raw()) == ExceptTypeTag::<E, A>::OK_TAG }
+    }
+
+    pub fn is_error(&self) -> bool {
+        !self.is_ok()
+    }
+
+    pub fn get(&self) -> Result<A::Output<'_, 'r>, E::Output<'_, 'r>> {
+        unsafe { ExceptTypeTag::<E, A>::from_element(self.as_mut_raw()) }
+    }
+}
+
+impl<'r, E: LeanArrayTypeTag, A: LeanArrayTypeTag> Object<'r, ExceptTypeTag<E, A>> {
+    pub fn from_result<R: Minimal, M: Modules, T: Into<A::Input<'r>>, U: Into<E::Input<'r>>>(
+        _runtime: &'r Runtime<R, M>,
+        value: Result<T, U>,
+    ) -> Self {
+        let object = ExceptTypeTag::<E, A>::into_element(value.map(Into::into).map_err(Into::into));
+        unsafe { Self::new(object) }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        <Self as Borrow<Obj<_>>>::borrow(self).is_ok()
+    }
+
+    pub fn is_error(&self) -> bool {
+        <Self as Borrow<Obj<_>>>::borrow(self).is_error()
+    }
+
+    pub fn get(&self) -> Result<A::Output<'_, 'r>, E::Output<'_, 'r>> {
+        <Self as Borrow<Obj<_>>>::borrow(self).get()
+    }
+}
+
+impl<'a, 'r, E: LeanArrayTypeTag, A: LeanArrayTypeTag> From<&'a Obj<'r, ExceptTypeTag<E, A>>>
+    for Result<A::Output<'a, 'r>, E::Output<'a, 'r>>
+{
+    fn from(value: &'a Obj<'r, ExceptTypeTag<E, A>>) -> Self {
+        value.get()
+    }
+}
+
+pub type LeanExcept<'r, E, A> = Object<'r, ExceptTypeTag<E, A>>;
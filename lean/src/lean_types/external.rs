@@ -0,0 +1,143 @@
+use std::any::TypeId;
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+use lean_sys::{
+    b_lean_obj_arg, lean_alloc_external, lean_apply_1, lean_dec, lean_external_class,
+    lean_get_external_class, lean_get_external_data, lean_inc, lean_register_external_class,
+};
+
+use super::{
+    Owner, Reference,
+    any::{AnyObj, CheckedTypeTag, LeanTypeTag, ObjectKind},
+    closure::abort_on_panic,
+    object::{Obj, Object},
+};
+use crate::{Minimal, Modules, Runtime};
+
+/// A Rust type whose values can be handed to Lean code as opaque external
+/// objects
+///
+/// Lean code may share external objects with other threads, so values must be
+/// thread-safe, and they are dropped on whichever thread frees the object.
+/// Types from other crates can be wrapped in a newtype to implement this
+/// trait.
+pub trait LeanExternal: Send + Sync + 'static {
+    /// Calls `visit` on every Lean object that this value holds, such as the
+    /// [`SharedObject`](super::object::SharedObject) fields of a handle that
+    /// caches Lean values
+    ///
+    /// Lean uses this to traverse the objects reachable from the external
+    /// object, for example when marking it as multi-threaded or persistent.
+    /// Values that hold no Lean objects can use the default implementation.
+    fn for_each_object(&self, visit: &mut dyn FnMut(&dyn Reference)) {
+        let _ = visit;
+    }
+}
+
+/// The type tag of Lean external objects that box a Rust value of type `T`
+///
+/// Lean code sees these objects as values of an opaque type, which it can only
+/// pass back to Rust. Each Rust type has its own external class, which is
+/// registered the first time a value of the type is boxed.
+pub struct ExternalTypeTag<T: LeanExternal>(PhantomData<T>);
+
+pub type ExternalObj<'r, T> = Obj<'r, ExternalTypeTag<T>>;
+pub type ExternalObject<'r, T> = Object<'r, ExternalTypeTag<T>>;
+
+impl<T: LeanExternal> LeanTypeTag for ExternalTypeTag<T> {
+    fn accepts_kind(kind: ObjectKind) -> bool {
+        kind == ObjectKind::External
+    }
+}
+
+unsafe impl<T: LeanExternal> CheckedTypeTag for ExternalTypeTag<T> {
+    fn matches(object: &AnyObj<'_>) -> bool {
+        // No object of `T` exists before its class is registered
+        Self::accepts_kind(object.object_kind())
+            && registered_class::<T>().is_some_and(
+                |class| unsafe { lean_get_external_class(object.as_mut_raw()) } == class,
+            )
+    }
+}
+
+/// A registered Lean external class
+pub(super) struct ExternalClass(pub(super) *mut lean_external_class);
+
+// External classes are immutable once registered
+unsafe impl Send for ExternalClass {}
+unsafe impl Sync for ExternalClass {}
+
+/// The external classes of the Rust types that have been boxed so far, which
+/// are never unregistered
+fn external_classes() -> &'static RwLock<HashMap<TypeId, ExternalClass>> {
+    static CLASSES: OnceLock<RwLock<HashMap<TypeId, ExternalClass>>> = OnceLock::new();
+    CLASSES.get_or_init(Default::default)
+}
+
+fn registered_class<T: LeanExternal>() -> Option<*mut lean_external_class> {
+    let classes = external_classes()
+        .read()
+        .unwrap_or_else(PoisonError::into_inner);
+    classes.get(&TypeId::of::<T>()).map(|class| class.0)
+}
+
+/// The external class of `T`, which is registered on first use
+fn external_class<T: LeanExternal>() -> *mut lean_external_class {
+    if let Some(class) = registered_class::<T>() {
+        return class;
+    }
+    let mut classes = external_classes()
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    classes
+        .entry(TypeId::of::<T>())
+        .or_insert_with(|| unsafe {
+            ExternalClass(lean_register_external_class(
+                Some(finalize_external::<T>),
+                Some(foreach_external::<T>),
+            ))
+        })
+        .0
+}
+
+unsafe extern "C" fn finalize_external<T: LeanExternal>(data: *mut c_void) {
+    abort_on_panic("Rust external object finalizer", || {
+        drop(unsafe { Box::from_raw(data.cast::<T>()) });
+    });
+}
+
+unsafe extern "C" fn foreach_external<T: LeanExternal>(data: *mut c_void, f: b_lean_obj_arg) {
+    abort_on_panic("Rust external object traversal", || {
+        let value = unsafe { &*data.cast::<T>() };
+        value.for_each_object(&mut |object| unsafe {
+            let object = object.as_mut_raw();
+            lean_inc(f);
+            lean_inc(object);
+            lean_dec(lean_apply_1(f, object));
+        });
+    });
+}
+
+impl<T: LeanExternal> Obj<'_, ExternalTypeTag<T>> {
+    /// Views the boxed Rust value
+    pub fn get(&self) -> &T {
+        unsafe { &*lean_get_external_data(self.as_mut_raw()).cast::<T>() }
+    }
+}
+
+impl<'r, T: LeanExternal> Object<'r, ExternalTypeTag<T>> {
+    /// Boxes a Rust value into a Lean external object, which drops the value
+    /// when it is freed
+    pub fn from_value<R: Minimal, M: Modules>(_runtime: &'r Runtime<R, M>, value: T) -> Self {
+        let data = Box::into_raw(Box::new(value));
+        unsafe { Self::new(lean_alloc_external(external_class::<T>(), data.cast())) }
+    }
+
+    pub fn get(&self) -> &T {
+        <Self as Borrow<Obj<_>>>::borrow(self).get()
+    }
+}
@@ -1,19 +1,37 @@
 use std::borrow::Borrow;
+use std::cmp::Ordering;
 use std::iter::FusedIterator;
 use std::mem;
+use std::ptr;
 use std::slice;
 
-use lean_sys::{lean_alloc_sarray, lean_float_array_cptr, lean_sarray_size};
+use lean_sys::{
+    lean_alloc_sarray, lean_copy_float_array, lean_float_array_cptr, lean_float_array_push,
+    lean_sarray_set_size, lean_sarray_size,
+};
 
 use super::{
-    Owner, Reference,
+    LeanFromIterator, Owner, Reference,
+    any::{AnyObj, CheckedTypeTag, LeanTypeTag, ObjectKind},
     object::{Obj, Object},
 };
 use crate::{Minimal, Modules, Runtime};
 
 pub enum FloatArrayTypeTag {}
 
-impl Obj<FloatArrayTypeTag> {
+impl LeanTypeTag for FloatArrayTypeTag {
+    fn accepts_kind(kind: ObjectKind) -> bool {
+        kind == ObjectKind::ScalarArray { elem_size: 8 }
+    }
+}
+
+unsafe impl CheckedTypeTag for FloatArrayTypeTag {
+    fn matches(object: &AnyObj<'_>) -> bool {
+        Self::accepts_kind(object.object_kind())
+    }
+}
+
+impl Obj<'_, FloatArrayTypeTag> {
     pub fn as_slice(&self) -> &[f64] {
         unsafe {
             let array = self.as_mut_raw();
@@ -28,14 +46,14 @@ impl Obj<FloatArrayTypeTag> {
     }
 }
 
-impl Object<FloatArrayTypeTag> {
+impl<'r> Object<'r, FloatArrayTypeTag> {
     pub fn from_exact_size_iterator<
         R: Minimal,
         M: Modules,
         T: Into<f64>,
         I: IntoIterator<Item = T>,
     >(
-        _runtime: &Runtime<R, M>,
+        _runtime: &'r Runtime<R, M>,
         data: I,
     ) -> Self
     where
@@ -62,7 +80,177 @@ impl Object<FloatArrayTypeTag> {
     pub fn iter(&self) -> impl ExactSizeIterator<Item = f64> + DoubleEndedIterator + FusedIterator {
         <Self as Borrow<Obj<_>>>::borrow(self).iter()
     }
+
+    /// Mutably view this array, unless it is shared with other owners
+    pub fn try_unique(&mut self) -> Option<FloatArrayMut<'_, 'r>> {
+        if self.is_exclusive() {
+            Some(FloatArrayMut { object: self })
+        } else {
+            None
+        }
+    }
+
+    /// Mutably view this array, first replacing it with a copy if it is shared
+    /// with other owners
+    pub fn make_mut(&mut self) -> FloatArrayMut<'_, 'r> {
+        if !self.is_exclusive() {
+            unsafe {
+                let array = lean_copy_float_array(self.as_mut_raw());
+                ptr::write(self, Self::new(array));
+            }
+        }
+        FloatArrayMut { object: self }
+    }
+}
+
+impl<'r, T: Into<f64>> LeanFromIterator<'r, T> for Object<'r, FloatArrayTypeTag> {
+    fn from_lean_iter<R: Minimal, M: Modules, I: IntoIterator<Item = T>>(
+        runtime: &'r Runtime<R, M>,
+        iter: I,
+    ) -> Self {
+        let iterator = iter.into_iter();
+        let mut builder = FloatArrayBuilder::with_capacity(runtime, iterator.size_hint().0);
+        builder.extend(iterator);
+        builder.build()
+    }
+}
+
+/// A builder for a `FloatArray` of unknown length
+///
+/// The array starts with a capacity hint and grows as needed.
+pub struct FloatArrayBuilder<'r> {
+    object: Object<'r, FloatArrayTypeTag>,
+}
+
+impl<'r> FloatArrayBuilder<'r> {
+    pub fn new<R: Minimal, M: Modules>(runtime: &'r Runtime<R, M>) -> Self {
+        Self::with_capacity(runtime, 0)
+    }
+
+    pub fn with_capacity<R: Minimal, M: Modules>(
+        _runtime: &'r Runtime<R, M>,
+        capacity: usize,
+    ) -> Self {
+        let object = unsafe { lean_alloc_sarray(mem::size_of::<f64>() as u32, 0, capacity) };
+        Self {
+            object: unsafe { Object::new(object) },
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.object.as_slice().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.object.as_slice().is_empty()
+    }
+
+    pub fn push<T: Into<f64>>(&mut self, value: T) -> &mut Self {
+        self.array_mut().push(value.into());
+        self
+    }
+
+    pub fn build(self) -> Object<'r, FloatArrayTypeTag> {
+        self.object
+    }
+
+    fn array_mut(&mut self) -> FloatArrayMut<'_, 'r> {
+        // The builder is the array's only owner
+        FloatArrayMut {
+            object: &mut self.object,
+        }
+    }
+}
+
+impl<T: Into<f64>> Extend<T> for FloatArrayBuilder<'_> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut array = self.array_mut();
+        for value in iter {
+            array.push(value.into());
+        }
+    }
+}
+
+/// A mutable view of an exclusively-owned `FloatArray`
+pub struct FloatArrayMut<'a, 'r> {
+    object: &'a mut Object<'r, FloatArrayTypeTag>,
+}
+
+impl FloatArrayMut<'_, '_> {
+    pub fn as_slice(&self) -> &[f64] {
+        self.object.as_slice()
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [f64] {
+        unsafe {
+            let array = self.object.as_mut_raw();
+            slice::from_raw_parts_mut(lean_float_array_cptr(array), lean_sarray_size(array))
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.as_slice().is_empty()
+    }
+
+    /// Replaces the element at `index`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set(&mut self, index: usize, value: f64) {
+        self.as_mut_slice()[index] = value;
+    }
+
+    /// Appends an element, growing the array's capacity if needed
+    pub fn push(&mut self, value: f64) {
+        unsafe {
+            // `lean_float_array_push()` consumes the array and may reallocate it
+            let array = lean_float_array_push(self.object.as_mut_raw(), value);
+            ptr::write(self.object, Object::new(array));
+        }
+    }
+
+    /// Removes the last element, unless the array is empty
+    pub fn pop(&mut self) -> Option<f64> {
+        let (&last, _) = self.as_slice().split_last()?;
+        unsafe { lean_sarray_set_size(self.object.as_mut_raw(), self.len() - 1) };
+        Some(last)
+    }
+
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.as_mut_slice().swap(a, b);
+    }
+}
+
+/// Compares elements as floats, so arrays that contain NaN are not equal to
+/// themselves
+impl PartialEq for Obj<'_, FloatArrayTypeTag> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl PartialOrd for Obj<'_, FloatArrayTypeTag> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl PartialEq for Object<'_, FloatArrayTypeTag> {
+    fn eq(&self, other: &Self) -> bool {
+        <Self as Borrow<Obj<_>>>::borrow(self) == <Self as Borrow<Obj<_>>>::borrow(other)
+    }
+}
+
+impl PartialOrd for Object<'_, FloatArrayTypeTag> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        <Self as Borrow<Obj<_>>>::borrow(self).partial_cmp(<Self as Borrow<Obj<_>>>::borrow(other))
+    }
 }
 
-pub type FloatArr = Obj<FloatArrayTypeTag>;
-pub type FloatArray = Object<FloatArrayTypeTag>;
+pub type FloatArr<'r> = Obj<'r, FloatArrayTypeTag>;
+pub type FloatArray<'r> = Object<'r, FloatArrayTypeTag>;
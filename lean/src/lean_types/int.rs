@@ -0,0 +1,295 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use lean_sys::{
+    lean_int_add, lean_int_div, lean_int_ediv, lean_int_emod, lean_int_eq, lean_int_lt,
+    lean_int_mod, lean_int_mul, lean_int_neg, lean_int_sub, lean_int64_to_int, lean_is_scalar,
+    lean_nat_abs, lean_nat_to_int, lean_scalar_to_int64,
+};
+use num_bigint::{BigInt, Sign};
+
+use super::{
+    Owner, Reference,
+    any::{AnyObj, CheckedTypeTag, LeanTypeTag, ObjectKind},
+    nat::{LeanNat, NumberOutOfRangeError, apply_borrowed},
+    object::{Obj, Object},
+};
+use crate::{Minimal, Modules, Runtime};
+
+/// The type tag of Lean's arbitrary-precision integers
+///
+/// Small integers are represented as scalars, whereas larger integers are
+/// GMP-backed objects. All functions for this type transparently handle both
+/// representations.
+pub enum IntTypeTag {}
+
+impl LeanTypeTag for IntTypeTag {
+    fn accepts_kind(kind: ObjectKind) -> bool {
+        matches!(kind, ObjectKind::Scalar(_) | ObjectKind::Mpz)
+    }
+}
+
+unsafe impl CheckedTypeTag for IntTypeTag {
+    fn matches(object: &AnyObj<'_>) -> bool {
+        Self::accepts_kind(object.object_kind())
+    }
+}
+
+pub type Int<'r> = Obj<'r, IntTypeTag>;
+pub type LeanInt<'r> = Object<'r, IntTypeTag>;
+
+impl<'r> Obj<'r, IntTypeTag> {
+    /// Returns `true` if the number is stored as a scalar rather than as a
+    /// GMP-backed object
+    pub fn is_scalar(&self) -> bool {
+        unsafe { lean_is_scalar(self.as_mut_raw()) }
+    }
+
+    pub fn is_negative(&self) -> bool {
+        unsafe {
+            let zero = lean_int64_to_int(0);
+            lean_int_lt(self.as_mut_raw(), zero)
+        }
+    }
+
+    /// The absolute value of this integer, as computed by Lean's `Int.natAbs`
+    pub fn nat_abs(&self) -> LeanNat<'r> {
+        unsafe { LeanNat::new(lean_nat_abs(self.as_mut_raw())) }
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        unsafe {
+            let int = self.as_mut_raw();
+            if lean_is_scalar(int) {
+                return Some(lean_scalar_to_int64(int));
+            }
+        }
+        let magnitude = self.nat_abs().to_u64()?;
+        if self.is_negative() {
+            0_i64.checked_sub_unsigned(magnitude)
+        } else {
+            magnitude.try_into().ok()
+        }
+    }
+
+    pub fn to_bigint(&self) -> BigInt {
+        let sign = if self.is_negative() {
+            Sign::Minus
+        } else {
+            Sign::Plus
+        };
+        BigInt::from_biguint(sign, self.nat_abs().to_biguint())
+    }
+
+    /// Euclidean division, which is the semantics of `/` on `Int` in Lean
+    pub fn div_euclid(&self, rhs: &Self) -> Object<'r, IntTypeTag> {
+        unsafe { apply_borrowed(lean_int_ediv, self, rhs) }
+    }
+
+    /// Euclidean remainder, which is the semantics of `%` on `Int` in Lean
+    pub fn rem_euclid(&self, rhs: &Self) -> Object<'r, IntTypeTag> {
+        unsafe { apply_borrowed(lean_int_emod, self, rhs) }
+    }
+}
+
+impl<'r> Object<'r, IntTypeTag> {
+    pub fn from_i64<R: Minimal, M: Modules>(_runtime: &'r Runtime<R, M>, value: i64) -> Self {
+        unsafe { Self::new(lean_int64_to_int(value)) }
+    }
+
+    /// Converts a natural number to an integer, as Lean's `Int.ofNat`
+    pub fn from_nat<R: Minimal, M: Modules>(
+        _runtime: &'r Runtime<R, M>,
+        value: LeanNat<'r>,
+    ) -> Self {
+        unsafe { Self::new(lean_nat_to_int(value.into_raw())) }
+    }
+
+    pub fn from_bigint<R: Minimal, M: Modules>(runtime: &'r Runtime<R, M>, value: &BigInt) -> Self {
+        let magnitude = Self::from_nat(runtime, LeanNat::from_biguint(runtime, value.magnitude()));
+        match value.sign() {
+            Sign::Minus => -magnitude,
+            Sign::NoSign | Sign::Plus => magnitude,
+        }
+    }
+
+    pub fn is_scalar(&self) -> bool {
+        <Self as Borrow<Obj<_>>>::borrow(self).is_scalar()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        <Self as Borrow<Obj<_>>>::borrow(self).is_negative()
+    }
+
+    pub fn nat_abs(&self) -> LeanNat<'r> {
+        <Self as Borrow<Obj<_>>>::borrow(self).nat_abs()
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        <Self as Borrow<Obj<_>>>::borrow(self).to_i64()
+    }
+
+    pub fn to_bigint(&self) -> BigInt {
+        <Self as Borrow<Obj<_>>>::borrow(self).to_bigint()
+    }
+
+    pub fn div_euclid(&self, rhs: &Self) -> Self {
+        <Self as Borrow<Obj<_>>>::borrow(self).div_euclid(rhs.borrow())
+    }
+
+    pub fn rem_euclid(&self, rhs: &Self) -> Self {
+        <Self as Borrow<Obj<_>>>::borrow(self).rem_euclid(rhs.borrow())
+    }
+}
+
+impl TryFrom<&Obj<'_, IntTypeTag>> for i64 {
+    type Error = NumberOutOfRangeError;
+
+    fn try_from(value: &Obj<'_, IntTypeTag>) -> Result<Self, Self::Error> {
+        value.to_i64().ok_or(NumberOutOfRangeError)
+    }
+}
+
+impl From<&Obj<'_, IntTypeTag>> for BigInt {
+    fn from(value: &Obj<'_, IntTypeTag>) -> Self {
+        value.to_bigint()
+    }
+}
+
+/// Implements a binary operator for borrowed and owned integers using a Lean
+/// runtime function that borrows both of its arguments
+macro_rules! impl_int_binary_operator {
+    ($trait:ident, $method:ident, $lean_function:ident) => {
+        impl<'r> $trait<&Obj<'r, IntTypeTag>> for &Obj<'r, IntTypeTag> {
+            type Output = Object<'r, IntTypeTag>;
+
+            fn $method(self, rhs: &Obj<'r, IntTypeTag>) -> Self::Output {
+                unsafe { apply_borrowed($lean_function, self, rhs) }
+            }
+        }
+
+        impl<'r> $trait<&Object<'r, IntTypeTag>> for &Object<'r, IntTypeTag> {
+            type Output = Object<'r, IntTypeTag>;
+
+            fn $method(self, rhs: &Object<'r, IntTypeTag>) -> Self::Output {
+                <Object<'r, IntTypeTag> as Borrow<Obj<_>>>::borrow(self).$method(rhs.borrow())
+            }
+        }
+
+        impl<'r> $trait for Object<'r, IntTypeTag> {
+            type Output = Object<'r, IntTypeTag>;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                (&self).$method(&rhs)
+            }
+        }
+    };
+}
+
+// Division and remainder round towards zero, as for Rust's primitive integers,
+// but division by zero returns zero, as in Lean. See `div_euclid()` and
+// `rem_euclid()` for the semantics of Lean's `/` and `%` operators.
+impl_int_binary_operator!(Add, add, lean_int_add);
+impl_int_binary_operator!(Sub, sub, lean_int_sub);
+impl_int_binary_operator!(Mul, mul, lean_int_mul);
+impl_int_binary_operator!(Div, div, lean_int_div);
+impl_int_binary_operator!(Rem, rem, lean_int_mod);
+
+impl<'r> Neg for &Obj<'r, IntTypeTag> {
+    type Output = Object<'r, IntTypeTag>;
+
+    fn neg(self) -> Self::Output {
+        unsafe { Object::new(lean_int_neg(self.as_mut_raw())) }
+    }
+}
+
+impl<'r> Neg for &Object<'r, IntTypeTag> {
+    type Output = Object<'r, IntTypeTag>;
+
+    fn neg(self) -> Self::Output {
+        -<Object<'r, IntTypeTag> as Borrow<Obj<_>>>::borrow(self)
+    }
+}
+
+impl<'r> Neg for Object<'r, IntTypeTag> {
+    type Output = Object<'r, IntTypeTag>;
+
+    fn neg(self) -> Self::Output {
+        -&self
+    }
+}
+
+impl PartialEq for Obj<'_, IntTypeTag> {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { lean_int_eq(self.as_mut_raw(), other.as_mut_raw()) }
+    }
+}
+
+impl Eq for Obj<'_, IntTypeTag> {}
+
+impl PartialOrd for Obj<'_, IntTypeTag> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Obj<'_, IntTypeTag> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        unsafe {
+            let (a, b) = (self.as_mut_raw(), other.as_mut_raw());
+            if lean_int_eq(a, b) {
+                Ordering::Equal
+            } else if lean_int_lt(a, b) {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        }
+    }
+}
+
+impl PartialEq for Object<'_, IntTypeTag> {
+    fn eq(&self, other: &Self) -> bool {
+        <Self as Borrow<Obj<_>>>::borrow(self) == other.borrow()
+    }
+}
+
+impl Eq for Object<'_, IntTypeTag> {}
+
+impl PartialOrd for Object<'_, IntTypeTag> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Object<'_, IntTypeTag> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        <Self as Borrow<Obj<_>>>::borrow(self).cmp(other.borrow())
+    }
+}
+
+impl fmt::Display for Obj<'_, IntTypeTag> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_bigint(), f)
+    }
+}
+
+impl fmt::Debug for Obj<'_, IntTypeTag> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for Object<'_, IntTypeTag> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(<Self as Borrow<Obj<_>>>::borrow(self), f)
+    }
+}
+
+impl fmt::Debug for Object<'_, IntTypeTag> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
@@ -0,0 +1,240 @@
+use std::borrow::Borrow;
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use lean_sys::{lean_inc, lean_io_error_to_string, lean_io_result_mk_error, lean_obj_res};
+
+use super::{
+    Owner, Reference,
+    any::{AnyObj, CheckedTypeTag, ObjectKind},
+    array::{CheckedArrayTypeTag, ObjectArrayTypeTag},
+    ctor::{Ctor, CtorLayout, LeanCtorTypeTag, ObjectField, ScalarField},
+    object::{Obj, Object},
+    option::OptionTypeTag,
+    string::{LeanString, StringTypeTag},
+};
+use crate::{LeanIoError, LeanIoErrorKind};
+
+/// The type tag of Lean's `IO.Error`
+///
+/// An owned `IO.Error` object can be re-raised in Lean, whereas
+/// [`LeanIoError`] is its decoded Rust counterpart.
+pub enum IoErrorTypeTag {}
+
+const FILE_ERROR_LAYOUT: CtorLayout = CtorLayout::new(2, 0, 4);
+const OS_ERROR_LAYOUT: CtorLayout = CtorLayout::new(1, 0, 4);
+
+unsafe impl LeanCtorTypeTag for IoErrorTypeTag {
+    const LAYOUTS: &'static [CtorLayout] = &[
+        FILE_ERROR_LAYOUT,
+        OS_ERROR_LAYOUT,
+        OS_ERROR_LAYOUT,
+        OS_ERROR_LAYOUT,
+        OS_ERROR_LAYOUT,
+        OS_ERROR_LAYOUT,
+        OS_ERROR_LAYOUT,
+        OS_ERROR_LAYOUT,
+        OS_ERROR_LAYOUT,
+        OS_ERROR_LAYOUT,
+        FILE_ERROR_LAYOUT,
+        FILE_ERROR_LAYOUT,
+        FILE_ERROR_LAYOUT,
+        FILE_ERROR_LAYOUT,
+        FILE_ERROR_LAYOUT,
+        FILE_ERROR_LAYOUT,
+        FILE_ERROR_LAYOUT,
+        CtorLayout::new(0, 0, 0),
+        CtorLayout::new(1, 0, 0),
+    ];
+}
+
+unsafe impl CheckedTypeTag for IoErrorTypeTag {
+    fn matches(object: &AnyObj<'_>) -> bool {
+        let ObjectKind::Ctor { tag, num_objs } = object.object_kind() else {
+            return object.object_kind() == ObjectKind::Scalar(17);
+        };
+        let is_string = |index| StringTypeTag::matches(unsafe { object.ctor_field(index) });
+        let is_optional_string = |index| {
+            OptionTypeTag::<ObjectArrayTypeTag<StringTypeTag>>::matches_element(unsafe {
+                object.ctor_field(index)
+            })
+        };
+        match tag {
+            0 | 12..=16 => num_objs == 2 && is_optional_string(0) && is_string(1),
+            10 | 11 => num_objs == 2 && is_string(0) && is_string(1),
+            1..=9 | 18 => num_objs == 1 && is_string(0),
+            _ => false,
+        }
+    }
+}
+
+/// The `osCode` field of every constructor that has one
+const OS_CODE: ScalarField<u32> = unsafe { ScalarField::new(0) };
+
+fn string_field(ctor: &Ctor<'_, '_, IoErrorTypeTag>, index: u32) -> String {
+    let field = unsafe { ObjectField::<StringTypeTag>::new(index) };
+    ctor.object(field).as_str().to_owned()
+}
+
+fn optional_string_field(ctor: &Ctor<'_, '_, IoErrorTypeTag>, index: u32) -> Option<String> {
+    let field =
+        unsafe { ObjectField::<OptionTypeTag<ObjectArrayTypeTag<StringTypeTag>>>::new(index) };
+    ctor.object(field)
+        .get()
+        .map(|string| string.as_str().to_owned())
+}
+
+impl Obj<'_, IoErrorTypeTag> {
+    /// Decodes the constructor of this error and its fields
+    pub fn kind(&self) -> LeanIoErrorKind {
+        let Some(ctor) = self.as_ctor() else {
+            return match self.ctor_tag() {
+                17 => LeanIoErrorKind::UnexpectedEof,
+                tag => panic!("invalid constructor tag {tag} for `IO.Error`"),
+            };
+        };
+        let os_code = || ctor.scalar(OS_CODE);
+        match ctor.tag() {
+            0 => LeanIoErrorKind::AlreadyExists {
+                filename: optional_string_field(&ctor, 0),
+                os_code: os_code(),
+                details: string_field(&ctor, 1),
+            },
+            1 => LeanIoErrorKind::OtherError {
+                os_code: os_code(),
+                details: string_field(&ctor, 0),
+            },
+            2 => LeanIoErrorKind::ResourceBusy {
+                os_code: os_code(),
+                details: string_field(&ctor, 0),
+            },
+            3 => LeanIoErrorKind::ResourceVanished {
+                os_code: os_code(),
+                details: string_field(&ctor, 0),
+            },
+            4 => LeanIoErrorKind::UnsupportedOperation {
+                os_code: os_code(),
+                details: string_field(&ctor, 0),
+            },
+            5 => LeanIoErrorKind::HardwareFault {
+                os_code: os_code(),
+                details: string_field(&ctor, 0),
+            },
+            6 => LeanIoErrorKind::UnsatisfiedConstraints {
+                os_code: os_code(),
+                details: string_field(&ctor, 0),
+            },
+            7 => LeanIoErrorKind::IllegalOperation {
+                os_code: os_code(),
+                details: string_field(&ctor, 0),
+            },
+            8 => LeanIoErrorKind::ProtocolError {
+                os_code: os_code(),
+                details: string_field(&ctor, 0),
+            },
+            9 => LeanIoErrorKind::TimeExpired {
+                os_code: os_code(),
+                details: string_field(&ctor, 0),
+            },
+            10 => LeanIoErrorKind::Interrupted {
+                filename: string_field(&ctor, 0),
+                os_code: os_code(),
+                details: string_field(&ctor, 1),
+            },
+            11 => LeanIoErrorKind::NoFileOrDirectory {
+                filename: string_field(&ctor, 0),
+                os_code: os_code(),
+                details: string_field(&ctor, 1),
+            },
+            12 => LeanIoErrorKind::InvalidArgument {
+                filename: optional_string_field(&ctor, 0),
+                os_code: os_code(),
+                details: string_field(&ctor, 1),
+            },
+            13 => LeanIoErrorKind::PermissionDenied {
+                filename: optional_string_field(&ctor, 0),
+                os_code: os_code(),
+                details: string_field(&ctor, 1),
+            },
+            14 => LeanIoErrorKind::ResourceExhausted {
+                filename: optional_string_field(&ctor, 0),
+                os_code: os_code(),
+                details: string_field(&ctor, 1),
+            },
+            15 => LeanIoErrorKind::InappropriateType {
+                filename: optional_string_field(&ctor, 0),
+                os_code: os_code(),
+                details: string_field(&ctor, 1),
+            },
+            16 => LeanIoErrorKind::NoSuchThing {
+                filename: optional_string_field(&ctor, 0),
+                os_code: os_code(),
+                details: string_field(&ctor, 1),
+            },
+            18 => LeanIoErrorKind::UserError {
+                message: string_field(&ctor, 0),
+            },
+            tag => panic!("invalid constructor tag {tag} for `IO.Error`"),
+        }
+    }
+
+    /// The closest [`io::ErrorKind`], see [`LeanIoErrorKind::io_error_kind()`]
+    pub fn io_error_kind(&self) -> io::ErrorKind {
+        self.kind().io_error_kind()
+    }
+
+    /// The message that Lean's `IO.Error.toString` produces
+    pub fn message(&self) -> String {
+        unsafe {
+            let error = self.as_mut_raw();
+            lean_inc(error);
+            let message = LeanString::new(lean_io_error_to_string(error));
+            message.into()
+        }
+    }
+
+    /// Decodes this error into a Rust error that does not depend on the Lean
+    /// runtime
+    pub fn to_error(&self) -> LeanIoError {
+        LeanIoError::new(self.kind(), self.message())
+    }
+}
+
+impl Object<'_, IoErrorTypeTag> {
+    pub fn kind(&self) -> LeanIoErrorKind {
+        <Self as Borrow<Obj<_>>>::borrow(self).kind()
+    }
+
+    pub fn io_error_kind(&self) -> io::ErrorKind {
+        <Self as Borrow<Obj<_>>>::borrow(self).io_error_kind()
+    }
+
+    pub fn message(&self) -> String {
+        <Self as Borrow<Obj<_>>>::borrow(self).message()
+    }
+
+    pub fn to_error(&self) -> LeanIoError {
+        <Self as Borrow<Obj<_>>>::borrow(self).to_error()
+    }
+
+    /// Re-raises this error by wrapping it in an `IO` result, which can be
+    /// returned from an `IO` action
+    pub fn into_io_result(self) -> lean_obj_res {
+        unsafe { lean_io_result_mk_error(self.into_raw()) }
+    }
+}
+
+impl fmt::Debug for Object<'_, IoErrorTypeTag> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.kind().fmt(f)
+    }
+}
+
+impl fmt::Display for Object<'_, IoErrorTypeTag> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message())
+    }
+}
+
+impl Error for Object<'_, IoErrorTypeTag> {}
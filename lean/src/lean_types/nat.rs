@@ -0,0 +1,287 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Rem, Shl, Shr, Sub};
+
+use lean_sys::{
+    b_lean_obj_arg, lean_box, lean_dec, lean_is_scalar, lean_nat_add, lean_nat_div, lean_nat_eq,
+    lean_nat_land, lean_nat_le, lean_nat_lor, lean_nat_lt, lean_nat_lxor, lean_nat_mod,
+    lean_nat_mul, lean_nat_shiftl, lean_nat_shiftr, lean_nat_sub, lean_obj_res, lean_uint64_of_nat,
+    lean_uint64_to_nat, lean_unbox, lean_usize_to_nat,
+};
+use num_bigint::BigUint;
+
+use super::{
+    Owner, Reference,
+    any::{AnyObj, CheckedTypeTag, LeanTypeTag, ObjectKind},
+    object::{Obj, Object},
+};
+use crate::{Minimal, Modules, Runtime};
+
+/// The type tag of Lean's arbitrary-precision natural numbers
+///
+/// Natural numbers up to `usize::MAX >> 1` are represented as scalars, whereas
+/// larger numbers are GMP-backed objects. All functions for this type
+/// transparently handle both representations.
+pub enum NatTypeTag {}
+
+impl LeanTypeTag for NatTypeTag {
+    fn accepts_kind(kind: ObjectKind) -> bool {
+        matches!(kind, ObjectKind::Scalar(_) | ObjectKind::Mpz)
+    }
+}
+
+unsafe impl CheckedTypeTag for NatTypeTag {
+    fn matches(object: &AnyObj<'_>) -> bool {
+        Self::accepts_kind(object.object_kind())
+    }
+}
+
+pub type Nat<'r> = Obj<'r, NatTypeTag>;
+pub type LeanNat<'r> = Object<'r, NatTypeTag>;
+
+/// An error returned when a Lean natural number or integer does not fit in the
+/// target Rust integer type
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("Lean number out of range of the target integer type")]
+pub struct NumberOutOfRangeError;
+
+/// Applies a binary Lean runtime function that borrows both of its arguments
+///
+/// # Safety
+///
+/// `f` must return an owned object of the same type as its arguments.
+pub(super) unsafe fn apply_borrowed<'r, TypeTag: LeanTypeTag>(
+    f: unsafe extern "C" fn(b_lean_obj_arg, b_lean_obj_arg) -> lean_obj_res,
+    a: &Obj<'r, TypeTag>,
+    b: &Obj<'r, TypeTag>,
+) -> Object<'r, TypeTag> {
+    unsafe { Object::new(f(a.as_mut_raw(), b.as_mut_raw())) }
+}
+
+impl Obj<'_, NatTypeTag> {
+    /// Returns `true` if the number is stored as a scalar rather than as a
+    /// GMP-backed object
+    pub fn is_scalar(&self) -> bool {
+        unsafe { lean_is_scalar(self.as_mut_raw()) }
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        unsafe {
+            let nat = self.as_mut_raw();
+            if lean_is_scalar(nat) {
+                return Some(lean_unbox(nat) as u64);
+            }
+            let max = lean_uint64_to_nat(u64::MAX);
+            let fits = lean_nat_le(nat, max);
+            lean_dec(max);
+            fits.then(|| lean_uint64_of_nat(nat))
+        }
+    }
+
+    pub fn to_usize(&self) -> Option<usize> {
+        self.to_u64().and_then(|value| value.try_into().ok())
+    }
+
+    pub fn to_biguint(&self) -> BigUint {
+        if let Some(value) = self.to_u64() {
+            return BigUint::from(value);
+        }
+
+        // Extract 32-bit digits, least significant first, for `BigUint::new()`
+        let mut digits = Vec::new();
+        unsafe {
+            let mask = lean_uint64_to_nat(u32::MAX.into());
+            let shift = lean_box(32);
+            let zero = lean_box(0);
+            let mut remaining = self.to_owned();
+            while !lean_nat_eq(remaining.as_mut_raw(), zero) {
+                let digit = lean_nat_land(remaining.as_mut_raw(), mask);
+                digits.push(lean_uint64_of_nat(digit) as u32);
+                lean_dec(digit);
+                remaining = Object::new(lean_nat_shiftr(remaining.as_mut_raw(), shift));
+            }
+            lean_dec(mask);
+        }
+        BigUint::new(digits)
+    }
+}
+
+impl<'r> Object<'r, NatTypeTag> {
+    pub fn from_u64<R: Minimal, M: Modules>(_runtime: &'r Runtime<R, M>, value: u64) -> Self {
+        unsafe { Self::new(lean_uint64_to_nat(value)) }
+    }
+
+    pub fn from_usize<R: Minimal, M: Modules>(_runtime: &'r Runtime<R, M>, value: usize) -> Self {
+        unsafe { Self::new(lean_usize_to_nat(value)) }
+    }
+
+    pub fn from_biguint<R: Minimal, M: Modules>(
+        runtime: &'r Runtime<R, M>,
+        value: &BigUint,
+    ) -> Self {
+        let mut digits = value.iter_u64_digits().rev();
+        let Some(most_significant) = digits.next() else {
+            return Self::from_u64(runtime, 0);
+        };
+        let shift = unsafe { Self::new(lean_box(64)) };
+        digits.fold(Self::from_u64(runtime, most_significant), |nat, digit| {
+            let digit = Self::from_u64(runtime, digit);
+            &(&nat << &shift) | &digit
+        })
+    }
+
+    pub fn is_scalar(&self) -> bool {
+        <Self as Borrow<Obj<_>>>::borrow(self).is_scalar()
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        <Self as Borrow<Obj<_>>>::borrow(self).to_u64()
+    }
+
+    pub fn to_usize(&self) -> Option<usize> {
+        <Self as Borrow<Obj<_>>>::borrow(self).to_usize()
+    }
+
+    pub fn to_biguint(&self) -> BigUint {
+        <Self as Borrow<Obj<_>>>::borrow(self).to_biguint()
+    }
+}
+
+impl TryFrom<&Obj<'_, NatTypeTag>> for u64 {
+    type Error = NumberOutOfRangeError;
+
+    fn try_from(value: &Obj<'_, NatTypeTag>) -> Result<Self, Self::Error> {
+        value.to_u64().ok_or(NumberOutOfRangeError)
+    }
+}
+
+impl TryFrom<&Obj<'_, NatTypeTag>> for usize {
+    type Error = NumberOutOfRangeError;
+
+    fn try_from(value: &Obj<'_, NatTypeTag>) -> Result<Self, Self::Error> {
+        value.to_usize().ok_or(NumberOutOfRangeError)
+    }
+}
+
+impl From<&Obj<'_, NatTypeTag>> for BigUint {
+    fn from(value: &Obj<'_, NatTypeTag>) -> Self {
+        value.to_biguint()
+    }
+}
+
+/// Implements a binary operator for borrowed and owned natural numbers using a
+/// Lean runtime function that borrows both of its arguments
+macro_rules! impl_nat_binary_operator {
+    ($trait:ident, $method:ident, $lean_function:ident) => {
+        impl<'r> $trait<&Obj<'r, NatTypeTag>> for &Obj<'r, NatTypeTag> {
+            type Output = Object<'r, NatTypeTag>;
+
+            fn $method(self, rhs: &Obj<'r, NatTypeTag>) -> Self::Output {
+                unsafe { apply_borrowed($lean_function, self, rhs) }
+            }
+        }
+
+        impl<'r> $trait<&Object<'r, NatTypeTag>> for &Object<'r, NatTypeTag> {
+            type Output = Object<'r, NatTypeTag>;
+
+            fn $method(self, rhs: &Object<'r, NatTypeTag>) -> Self::Output {
+                <Object<'r, NatTypeTag> as Borrow<Obj<_>>>::borrow(self).$method(rhs.borrow())
+            }
+        }
+
+        impl<'r> $trait for Object<'r, NatTypeTag> {
+            type Output = Object<'r, NatTypeTag>;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                (&self).$method(&rhs)
+            }
+        }
+    };
+}
+
+// Subtraction saturates at zero, and division and remainder by zero return
+// zero and the dividend, respectively, as in Lean.
+impl_nat_binary_operator!(Add, add, lean_nat_add);
+impl_nat_binary_operator!(Sub, sub, lean_nat_sub);
+impl_nat_binary_operator!(Mul, mul, lean_nat_mul);
+impl_nat_binary_operator!(Div, div, lean_nat_div);
+impl_nat_binary_operator!(Rem, rem, lean_nat_mod);
+impl_nat_binary_operator!(BitAnd, bitand, lean_nat_land);
+impl_nat_binary_operator!(BitOr, bitor, lean_nat_lor);
+impl_nat_binary_operator!(BitXor, bitxor, lean_nat_lxor);
+impl_nat_binary_operator!(Shl, shl, lean_nat_shiftl);
+impl_nat_binary_operator!(Shr, shr, lean_nat_shiftr);
+
+impl PartialEq for Obj<'_, NatTypeTag> {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { lean_nat_eq(self.as_mut_raw(), other.as_mut_raw()) }
+    }
+}
+
+impl Eq for Obj<'_, NatTypeTag> {}
+
+impl PartialOrd for Obj<'_, NatTypeTag> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Obj<'_, NatTypeTag> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        unsafe {
+            let (a, b) = (self.as_mut_raw(), other.as_mut_raw());
+            if lean_nat_eq(a, b) {
+                Ordering::Equal
+            } else if lean_nat_lt(a, b) {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        }
+    }
+}
+
+impl PartialEq for Object<'_, NatTypeTag> {
+    fn eq(&self, other: &Self) -> bool {
+        <Self as Borrow<Obj<_>>>::borrow(self) == other.borrow()
+    }
+}
+
+impl Eq for Object<'_, NatTypeTag> {}
+
+impl PartialOrd for Object<'_, NatTypeTag> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Object<'_, NatTypeTag> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        <Self as Borrow<Obj<_>>>::borrow(self).cmp(other.borrow())
+    }
+}
+
+impl fmt::Display for Obj<'_, NatTypeTag> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_biguint(), f)
+    }
+}
+
+impl fmt::Debug for Obj<'_, NatTypeTag> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for Object<'_, NatTypeTag> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(<Self as Borrow<Obj<_>>>::borrow(self), f)
+    }
+}
+
+impl fmt::Debug for Object<'_, NatTypeTag> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
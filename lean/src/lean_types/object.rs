@@ -1,35 +1,106 @@
 use std::borrow::Borrow;
-use std::marker::PhantomData;
+use std::marker::{PhantomData, PhantomPinned};
+use std::ptr;
 
-use lean_sys::{b_lean_obj_arg, lean_dec, lean_inc, lean_obj_arg, lean_object};
+use lean_sys::{
+    b_lean_obj_arg, lean_dec, lean_inc, lean_is_exclusive, lean_is_persistent, lean_is_scalar,
+    lean_is_shared, lean_mark_mt, lean_mark_persistent, lean_obj_arg, lean_obj_res, lean_object,
+};
 
-use super::{Borrower, Owner, Reference};
+#[cfg(feature = "debug-checks")]
+use super::any::check_object_kind;
+use super::{Borrower, LeanValue, Owner, Reference, any::LeanTypeTag};
+use crate::{Minimal, Modules, Runtime, runtime::liveness};
 
 /// A borrowed Lean object
 ///
-/// This type behaves like a Rust reference to a Lean object.
-pub struct Obj<TypeTag>(*mut lean_object, PhantomData<TypeTag>);
+/// This type is only ever used behind references, like [`str`]: an `&Obj` has
+/// the same representation as a pointer to a Lean object, and its lifetime is
+/// bounded by the owner of the object. Boxed scalars are represented by
+/// references to the address that encodes them, which are never dereferenced.
+///
+/// `'r` is the lifetime of the [`Runtime`] that the object belongs to, which
+/// owned copies of the object inherit.
+#[repr(C)]
+pub struct Obj<'r, TypeTag> {
+    _opaque: [u8; 0],
+    _marker: PhantomData<(&'r (), TypeTag, *mut lean_object, PhantomPinned)>,
+}
+
+/// A borrowed Lean object that cannot outlive its owner
+///
+/// Owners lend these through [`Borrow`], and FFI callbacks obtain them from
+/// their borrowed arguments through [`Borrower::new()`].
+pub type ObjRef<'a, 'r, TypeTag> = &'a Obj<'r, TypeTag>;
 
-impl<TypeTag> ToOwned for Obj<TypeTag> {
-    type Owned = Object<TypeTag>;
+impl<'r, TypeTag: LeanTypeTag> ToOwned for Obj<'r, TypeTag> {
+    type Owned = Object<'r, TypeTag>;
 
     fn to_owned(&self) -> Self::Owned {
         unsafe {
-            lean_inc(self.0);
-            Object(Self(self.0, PhantomData))
+            let object = self.as_mut_raw();
+            lean_inc(object);
+            Object::new(object)
         }
     }
 }
 
-unsafe impl<TypeTag> Reference for Obj<TypeTag> {
+unsafe impl<TypeTag> Reference for Obj<'_, TypeTag> {
     unsafe fn as_mut_raw(&self) -> *mut lean_object {
-        self.0
+        ptr::from_ref(self).cast_mut().cast()
     }
 }
 
-unsafe impl<TypeTag> Borrower for Obj<TypeTag> {
-    unsafe fn new(obj: b_lean_obj_arg) -> Self {
-        Self(obj, PhantomData)
+unsafe impl<TypeTag: LeanTypeTag> Borrower for Obj<'_, TypeTag> {
+    unsafe fn new(obj: &b_lean_obj_arg) -> &Self {
+        #[cfg(feature = "debug-checks")]
+        check_object_kind::<TypeTag>(unsafe { Obj::from_raw(*obj) });
+        unsafe { Self::from_raw(*obj) }
+    }
+}
+
+impl<TypeTag> Obj<'_, TypeTag> {
+    /// Views a borrowed object with a lifetime chosen by the caller
+    ///
+    /// # Safety
+    ///
+    /// Callers must ensure that `obj` points to an object (or a scalar) of the
+    /// correct type that stays alive for `'a`, and that it belongs to a runtime
+    /// that lives at least as long as the brand of the returned reference.
+    pub(crate) unsafe fn from_raw<'a>(obj: b_lean_obj_arg) -> &'a Self {
+        unsafe { &*obj.cast_const().cast() }
+    }
+}
+
+/// Reference counting introspection
+///
+/// Scalars are not reference counted, so they are neither exclusive, shared
+/// nor persistent.
+impl<TypeTag> Obj<'_, TypeTag> {
+    /// Whether this object has a single owner, so that it can be mutated in
+    /// place
+    pub fn is_exclusive(&self) -> bool {
+        unsafe {
+            let object = self.as_mut_raw();
+            !lean_is_scalar(object) && lean_is_exclusive(object)
+        }
+    }
+
+    /// Whether this object has multiple owners or is shared between threads
+    pub fn is_shared(&self) -> bool {
+        unsafe {
+            let object = self.as_mut_raw();
+            !lean_is_scalar(object) && lean_is_shared(object)
+        }
+    }
+
+    /// Whether this object is persistent, such as the value of a closed term
+    /// computed during module initialization, in which case it is never freed
+    pub fn is_persistent(&self) -> bool {
+        unsafe {
+            let object = self.as_mut_raw();
+            !lean_is_scalar(object) && lean_is_persistent(object)
+        }
     }
 }
 
@@ -38,23 +109,30 @@ unsafe impl<TypeTag> Borrower for Obj<TypeTag> {
 /// This type behaves like a shared pointer to a Lean object. The object may
 /// have owners besides instances of this type unless the caller can guarantee
 /// otherwise.
-pub struct Object<TypeTag>(Obj<TypeTag>);
+///
+/// `'r` is the lifetime of the [`Runtime`] that the object belongs to, which
+/// constructors borrow, so that objects cannot be used after the runtime is
+/// finalized. See [`Object::detach()`] to move objects between threads, and
+/// [`SharedObject`] to share them.
+pub struct Object<'r, TypeTag>(*mut lean_object, PhantomData<(&'r (), TypeTag)>);
 
-impl<TypeTag> Borrow<Obj<TypeTag>> for Object<TypeTag> {
-    fn borrow(&self) -> &Obj<TypeTag> {
-        &self.0
+impl<'r, TypeTag> Borrow<Obj<'r, TypeTag>> for Object<'r, TypeTag> {
+    fn borrow(&self) -> &Obj<'r, TypeTag> {
+        unsafe { Obj::from_raw(self.0) }
     }
 }
 
-unsafe impl<TypeTag> Reference for Object<TypeTag> {
+unsafe impl<TypeTag> Reference for Object<'_, TypeTag> {
     unsafe fn as_mut_raw(&self) -> *mut lean_object {
-        unsafe { self.0.as_mut_raw() }
+        self.0
     }
 }
 
-unsafe impl<TypeTag> Owner<Obj<TypeTag>> for Object<TypeTag> {
+unsafe impl<'r, TypeTag: LeanTypeTag> Owner<Obj<'r, TypeTag>> for Object<'r, TypeTag> {
     unsafe fn new(obj: lean_obj_arg) -> Self {
-        Self(unsafe { Obj::new(obj) })
+        #[cfg(feature = "debug-checks")]
+        check_object_kind::<TypeTag>(unsafe { Obj::from_raw(obj) });
+        Self(obj, PhantomData)
     }
 
     fn into_raw(self) -> *mut lean_object {
@@ -64,12 +142,175 @@ unsafe impl<TypeTag> Owner<Obj<TypeTag>> for Object<TypeTag> {
     }
 
     fn share(&self) -> Self {
-        self.0.to_owned()
+        <Self as Borrow<Obj<_>>>::borrow(self).to_owned()
+    }
+}
+
+impl<TypeTag> Object<'_, TypeTag> {
+    pub fn is_exclusive(&self) -> bool {
+        <Self as Borrow<Obj<_>>>::borrow(self).is_exclusive()
+    }
+
+    pub fn is_shared(&self) -> bool {
+        <Self as Borrow<Obj<_>>>::borrow(self).is_shared()
+    }
+
+    pub fn is_persistent(&self) -> bool {
+        <Self as Borrow<Obj<_>>>::borrow(self).is_persistent()
     }
 }
 
-impl<TypeTag> Drop for Object<TypeTag> {
+impl<TypeTag> Drop for Object<'_, TypeTag> {
     fn drop(&mut self) {
         unsafe { lean_dec(self.as_mut_raw()) };
     }
 }
+
+impl<TypeTag: LeanTypeTag> Object<'_, TypeTag> {
+    /// Detaches this object from its runtime, so that it can be moved to
+    /// another thread, such as into a
+    /// [`run_in_thread_with_lean_runtime()`](crate::run_in_thread_with_lean_runtime)
+    /// closure
+    ///
+    /// The object and the objects reachable from it are marked as
+    /// multi-threaded, so their reference counts are updated atomically from
+    /// then on.
+    pub fn detach(self) -> DetachedObject<TypeTag> {
+        let object = self.into_raw();
+        unsafe { lean_mark_mt(object) };
+        DetachedObject(object, PhantomData)
+    }
+
+    /// Shares this object between threads, see [`SharedObject::new()`]
+    pub fn into_shared(self) -> SharedObject<TypeTag> {
+        SharedObject::new(self)
+    }
+
+    /// Shares this object between threads for the rest of the process, see
+    /// [`SharedObject::new_persistent()`]
+    pub fn into_persistent(self) -> SharedObject<TypeTag> {
+        SharedObject::new_persistent(self)
+    }
+}
+
+/// An owned Lean object that does not belong to a runtime
+///
+/// Detached objects can be sent to other threads, but they must be attached to
+/// a runtime before they can be used. They can be dropped on any thread: on
+/// threads without a runtime, the object is freed later by a thread that has
+/// one, and objects that are dropped after the runtime is finalized are
+/// leaked.
+pub struct DetachedObject<TypeTag>(*mut lean_object, PhantomData<TypeTag>);
+
+unsafe impl<TypeTag> Send for DetachedObject<TypeTag> {}
+
+impl<TypeTag: LeanTypeTag> DetachedObject<TypeTag> {
+    pub fn attach<'r, R: Minimal, M: Modules>(
+        self,
+        _runtime: &'r Runtime<R, M>,
+    ) -> Object<'r, TypeTag> {
+        let object = self.0;
+        std::mem::forget(self);
+        unsafe { Object::new(object) }
+    }
+}
+
+impl<TypeTag> Drop for DetachedObject<TypeTag> {
+    fn drop(&mut self) {
+        unsafe { liveness::release(self.0) };
+    }
+}
+
+/// Detached objects can be returned from code that runs on threads of Lean's
+/// task manager, which have no runtime to brand objects with
+unsafe impl<TypeTag> LeanValue for DetachedObject<TypeTag> {
+    fn into_boxed(self) -> lean_obj_res {
+        let object = self.0;
+        std::mem::forget(self);
+        object
+    }
+
+    unsafe fn from_boxed(boxed: lean_obj_arg) -> Self {
+        unsafe { lean_mark_mt(boxed) };
+        Self(boxed, PhantomData)
+    }
+}
+
+/// An owned Lean object that can be shared between threads
+///
+/// The object and the objects reachable from it are marked as multi-threaded,
+/// or as persistent, so it can be cloned and dropped from any thread. It must
+/// be borrowed with a runtime of the current thread to be used, which the
+/// threads started with
+/// [`run_in_thread_with_lean_runtime()`](crate::run_in_thread_with_lean_runtime)
+/// provide.
+///
+/// Shared objects can be dropped on any thread, including threads that are
+/// not initialized for Lean, such as those of an async executor, in which case
+/// the object is freed later by a thread that has a runtime. Shared objects
+/// may also outlive the runtime, for example in a `static`: the objects that
+/// are dropped after the runtime is finalized are leaked.
+pub struct SharedObject<TypeTag>(*mut lean_object, PhantomData<TypeTag>);
+
+// Multi-threaded objects are reference counted atomically and persistent
+// objects are not reference counted, and neither can be mutated in place
+unsafe impl<TypeTag> Send for SharedObject<TypeTag> {}
+unsafe impl<TypeTag> Sync for SharedObject<TypeTag> {}
+
+unsafe impl<TypeTag> Reference for SharedObject<TypeTag> {
+    unsafe fn as_mut_raw(&self) -> *mut lean_object {
+        self.0
+    }
+}
+
+impl<TypeTag: LeanTypeTag> SharedObject<TypeTag> {
+    /// Marks an object as multi-threaded, so that its reference counts are
+    /// updated atomically from then on
+    pub fn new(object: Object<'_, TypeTag>) -> Self {
+        let object = object.into_raw();
+        unsafe { lean_mark_mt(object) };
+        Self(object, PhantomData)
+    }
+
+    /// Marks an object as persistent, for constants that live as long as the
+    /// process
+    ///
+    /// Persistent objects are not reference counted, so sharing them is free,
+    /// but they are never freed, nor are the objects reachable from them.
+    pub fn new_persistent(object: Object<'_, TypeTag>) -> Self {
+        let object = object.into_raw();
+        unsafe { lean_mark_persistent(object) };
+        Self(object, PhantomData)
+    }
+
+    /// Borrows the object for use with a runtime of the current thread
+    pub fn get<'a, 'r, R: Minimal, M: Modules>(
+        &'a self,
+        _runtime: &'r Runtime<R, M>,
+    ) -> &'a Obj<'r, TypeTag> {
+        unsafe { Obj::from_raw(self.0) }
+    }
+
+    /// Creates a new owning reference to the object for use with a runtime of
+    /// the current thread
+    pub fn to_object<'r, R: Minimal, M: Modules>(
+        &self,
+        runtime: &'r Runtime<R, M>,
+    ) -> Object<'r, TypeTag> {
+        self.get(runtime).to_owned()
+    }
+}
+
+impl<TypeTag> Clone for SharedObject<TypeTag> {
+    fn clone(&self) -> Self {
+        // The object is leaked if the runtime has been finalized
+        liveness::with_live_runtime(|| unsafe { lean_inc(self.0) });
+        Self(self.0, PhantomData)
+    }
+}
+
+impl<TypeTag> Drop for SharedObject<TypeTag> {
+    fn drop(&mut self) {
+        unsafe { liveness::release(self.0) };
+    }
+}
@@ -0,0 +1,139 @@
+use std::borrow::Borrow;
+use std::marker::PhantomData;
+
+use lean_sys::{
+    b_lean_obj_arg, lean_alloc_ctor, lean_box, lean_ctor_get, lean_ctor_set, lean_dec, lean_inc,
+    lean_is_scalar, lean_obj_arg, lean_object,
+};
+
+use super::{
+    Owner, Reference,
+    any::{AnyObj, CheckedTypeTag, LeanTypeTag, ObjectKind},
+    array::{CheckedArrayTypeTag, LeanArrayTypeTag},
+    object::{Obj, Object},
+};
+use crate::{Minimal, Modules, Runtime};
+
+/// The type tag of Lean's `Option α`, where `T` is the type tag of `α`
+///
+/// `none` is represented as a scalar and `some` is represented as a
+/// constructor object with one field. `Option` can itself be used as an element
+/// type, allowing for arrays of options and nested options.
+pub struct OptionTypeTag<T: LeanArrayTypeTag>(PhantomData<T>);
+
+unsafe impl<T: LeanArrayTypeTag> LeanArrayTypeTag for OptionTypeTag<T> {
+    type Input<'r> = Option<T::Input<'r>>;
+    type Output<'a, 'r>
+        = Option<T::Output<'a, 'r>>
+    where
+        Self: 'a,
+        'r: 'a;
+
+    fn into_element(input: Self::Input<'_>) -> *mut lean_object {
+        match input {
+            None => unsafe { lean_box(0) },
+            Some(value) => unsafe {
+                let option = lean_alloc_ctor(1, 1, 0);
+                lean_ctor_set(option, 0, T::into_element(value));
+                option
+            },
+        }
+    }
+
+    unsafe fn from_element<'a, 'r>(element: b_lean_obj_arg) -> Self::Output<'a, 'r>
+    where
+        Self: 'a,
+        'r: 'a,
+    {
+        unsafe {
+            if lean_is_scalar(element) {
+                None
+            } else {
+                Some(T::from_element(lean_ctor_get(element, 0)))
+            }
+        }
+    }
+
+    unsafe fn take_element<'r>(element: lean_obj_arg) -> Self::Input<'r> {
+        unsafe {
+            if lean_is_scalar(element) {
+                None
+            } else {
+                let value = lean_ctor_get(element, 0);
+                lean_inc(value);
+                lean_dec(element);
+                Some(T::take_element(value))
+            }
+        }
+    }
+}
+
+impl<T: LeanArrayTypeTag> LeanTypeTag for OptionTypeTag<T> {
+    fn accepts_kind(kind: ObjectKind) -> bool {
+        matches!(
+            kind,
+            ObjectKind::Scalar(0)
+                | ObjectKind::Ctor {
+                    tag: 1,
+                    num_objs: 1
+                }
+        )
+    }
+}
+
+unsafe impl<T: CheckedArrayTypeTag> CheckedArrayTypeTag for OptionTypeTag<T> {
+    fn matches_element(element: &AnyObj<'_>) -> bool {
+        match element.object_kind() {
+            ObjectKind::Scalar(0) => true,
+            ObjectKind::Ctor {
+                tag: 1,
+                num_objs: 1,
+            } => T::matches_element(unsafe { element.ctor_field(0) }),
+            _ => false,
+        }
+    }
+}
+
+unsafe impl<T: CheckedArrayTypeTag> CheckedTypeTag for OptionTypeTag<T> {
+    fn matches(object: &AnyObj<'_>) -> bool {
+        Self::matches_element(object)
+    }
+}
+
+impl<'r, T: LeanArrayTypeTag> Obj<'r, OptionTypeTag<T>> {
+    pub fn is_some(&self) -> bool {
+        !self.is_none()
+    }
+
+    pub fn is_none(&self) -> bool {
+        unsafe { lean_is_scalar(self.as_mut_raw()) }
+    }
+
+    pub fn get(&self) -> Option<T::Output<'_, 'r>> {
+        unsafe { OptionTypeTag::<T>::from_element(self.as_mut_raw()) }
+    }
+}
+
+impl<'r, T: LeanArrayTypeTag> Object<'r, OptionTypeTag<T>> {
+    pub fn from_option<R: Minimal, M: Modules, U: Into<T::Input<'r>>>(
+        _runtime: &'r Runtime<R, M>,
+        value: Option<U>,
+    ) -> Self {
+        let object = OptionTypeTag::<T>::into_element(value.map(Into::into));
+        unsafe { Self::new(object) }
+    }
+
+    pub fn is_some(&self) -> bool {
+        <Self as Borrow<Obj<_>>>::borrow(self).is_some()
+    }
+
+    pub fn is_none(&self) -> bool {
+        <Self as Borrow<Obj<_>>>::borrow(self).is_none()
+    }
+
+    pub fn get(&self) -> Option<T::Output<'_, 'r>> {
+        <Self as Borrow<Obj<_>>>::borrow(self).get()
+    }
+}
+
+pub type LeanOption<'r, T> = Object<'r, OptionTypeTag<T>>;
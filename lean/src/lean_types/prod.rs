@@ -0,0 +1,144 @@
+use std::borrow::Borrow;
+use std::marker::PhantomData;
+
+use lean_sys::{
+    b_lean_obj_arg, lean_alloc_ctor, lean_ctor_get, lean_ctor_set, lean_dec, lean_inc,
+    lean_obj_arg, lean_object,
+};
+
+use super::{
+    Owner, Reference,
+    any::{AnyObj, CheckedTypeTag, LeanTypeTag, ObjectKind},
+    array::{CheckedArrayTypeTag, LeanArrayTypeTag},
+    object::{Obj, Object},
+};
+use crate::{Minimal, Modules, Runtime};
+
+/// The type tag of Lean's `α × β`, where `A` and `B` are the type tags of `α`
+/// and `β`, respectively
+///
+/// Pairs are represented as constructor objects with two fields. `Prod` can
+/// itself be used as an element type, allowing for arrays of pairs and nested
+/// pairs such as `α × β × γ`.
+pub struct ProdTypeTag<A: LeanArrayTypeTag, B: LeanArrayTypeTag>(PhantomData<(A, B)>);
+
+unsafe impl<A: LeanArrayTypeTag, B: LeanArrayTypeTag> LeanArrayTypeTag for ProdTypeTag<A, B> {
+    type Input<'r> = (A::Input<'r>, B::Input<'r>);
+    type Output<'a, 'r>
+        = (A::Output<'a, 'r>, B::Output<'a, 'r>)
+    where
+        Self: 'a,
+        'r: 'a;
+
+    fn into_element(input: Self::Input<'_>) -> *mut lean_object {
+        let (fst, snd) = input;
+        unsafe {
+            let prod = lean_alloc_ctor(0, 2, 0);
+            lean_ctor_set(prod, 0, A::into_element(fst));
+            lean_ctor_set(prod, 1, B::into_element(snd));
+            prod
+        }
+    }
+
+    unsafe fn from_element<'a, 'r>(element: b_lean_obj_arg) -> Self::Output<'a, 'r>
+    where
+        Self: 'a,
+        'r: 'a,
+    {
+        unsafe {
+            (
+                A::from_element(lean_ctor_get(element, 0)),
+                B::from_element(lean_ctor_get(element, 1)),
+            )
+        }
+    }
+
+    unsafe fn take_element<'r>(element: lean_obj_arg) -> Self::Input<'r> {
+        unsafe {
+            let fst = lean_ctor_get(element, 0);
+            let snd = lean_ctor_get(element, 1);
+            lean_inc(fst);
+            lean_inc(snd);
+            lean_dec(element);
+            (A::take_element(fst), B::take_element(snd))
+        }
+    }
+}
+
+impl<A: LeanArrayTypeTag, B: LeanArrayTypeTag> LeanTypeTag for ProdTypeTag<A, B> {
+    fn accepts_kind(kind: ObjectKind) -> bool {
+        kind == ObjectKind::Ctor {
+            tag: 0,
+            num_objs: 2,
+        }
+    }
+}
+
+unsafe impl<A: CheckedArrayTypeTag, B: CheckedArrayTypeTag> CheckedArrayTypeTag
+    for ProdTypeTag<A, B>
+{
+    fn matches_element(element: &AnyObj<'_>) -> bool {
+        element.object_kind()
+            == ObjectKind::Ctor {
+                tag: 0,
+                num_objs: 2,
+            }
+            && unsafe {
+                A::matches_element(element.ctor_field(0))
+                    && B::matches_element(element.ctor_field(1))
+            }
+    }
+}
+
+unsafe impl<A: CheckedArrayTypeTag, B: CheckedArrayTypeTag> CheckedTypeTag for ProdTypeTag<A, B> {
+    fn matches(object: &AnyObj<'_>) -> bool {
+        Self::matches_element(object)
+    }
+}
+
+impl<'r, A: LeanArrayTypeTag, B: LeanArrayTypeTag> Obj<'r, ProdTypeTag<A, B>> {
+    pub fn fst(&self) -> A::Output<'_, 'r> {
+        unsafe { A::from_element(lean_ctor_get(self.as_mut_raw(), 0)) }
+    }
+
+    pub fn snd(&self) -> B::Output<'_, 'r> {
+        unsafe { B::from_element(lean_ctor_get(self.as_mut_raw(), 1)) }
+    }
+
+    pub fn get(&self) -> (A::Output<'_, 'r>, B::Output<'_, 'r>) {
+        unsafe { ProdTypeTag::<A, B>::from_element(self.as_mut_raw()) }
+    }
+}
+
+impl<'r, A: LeanArrayTypeTag, B: LeanArrayTypeTag> Object<'r, ProdTypeTag<A, B>> {
+    pub fn from_tuple<R: Minimal, M: Modules, T: Into<A::Input<'r>>, U: Into<B::Input<'r>>>(
+        _runtime: &'r Runtime<R, M>,
+        value: (T, U),
+    ) -> Self {
+        let (fst, snd) = value;
+        let object = ProdTypeTag::<A, B>::into_element((fst.into(), snd.into()));
+        unsafe { Self::new(object) }
+    }
+
+    pub fn fst(&self) -> A::Output<'_, 'r> {
+        <Self as Borrow<Obj<_>>>::borrow(self).fst()
+    }
+
+    pub fn snd(&self) -> B::Output<'_, 'r> {
+        <Self as Borrow<Obj<_>>>::borrow(self).snd()
+    }
+
+    pub fn get(&self) -> (A::Output<'_, 'r>, B::Output<'_, 'r>) {
+        <Self as Borrow<Obj<_>>>::borrow(self).get()
+    }
+}
+
+impl<'a, 'r, A: LeanArrayTypeTag, B: LeanArrayTypeTag> From<&'a Obj<'r, ProdTypeTag<A, B>>>
+    for (A::Output<'a, 'r>, B::Output<'a, 'r>)
+{
+    fn from(value: &'a Obj<'r, ProdTypeTag<A, B>>) -> Self {
+        value.get()
+    }
+}
+
+pub type LeanProd<'r, A, B> = Object<'r, ProdTypeTag<A, B>>;
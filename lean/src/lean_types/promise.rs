@@ -0,0 +1,80 @@
+use std::borrow::Borrow;
+use std::marker::PhantomData;
+
+use lean_sys::{
+    lean_inc, lean_io_promise_new, lean_io_promise_resolve, lean_io_promise_result_opt,
+};
+
+use super::{
+    LeanValue, Owner, Reference,
+    any::{LeanTypeTag, ObjectKind},
+    object::{Obj, Object},
+    task::LeanTask,
+};
+use crate::io::IoResult;
+use crate::{Minimal, Modules, Runtime};
+
+/// The type tag of Lean's `IO.Promise α`, where `T` is the Rust type of `α`
+///
+/// A promise is a task that Rust code resolves: Lean code can wait for its
+/// result like for any other task, and is woken once a value is pushed into
+/// it. Promises can be resolved from other threads through a
+/// [`SharedObject`](super::object::SharedObject), in which case `T` must not
+/// be branded, as for the results of [`Runtime::spawn_task()`].
+pub struct PromiseTypeTag<T: LeanValue>(PhantomData<fn(T) -> T>);
+
+pub type Promise<'r, T> = Obj<'r, PromiseTypeTag<T>>;
+pub type LeanPromise<'r, T> = Object<'r, PromiseTypeTag<T>>;
+
+impl<T: LeanValue> LeanTypeTag for PromiseTypeTag<T> {
+    fn accepts_kind(kind: ObjectKind) -> bool {
+        kind == ObjectKind::Promise
+    }
+}
+
+impl<'r, T: LeanValue> Obj<'r, PromiseTypeTag<T>> {
+    /// Resolves the promise with `value`, like `IO.Promise.resolve`
+    ///
+    /// Only the first value is kept: resolving a promise that has already
+    /// been resolved has no effect.
+    pub fn resolve(&self, value: T) {
+        unsafe {
+            IoResult::run(|world| {
+                lean_io_promise_resolve(value.into_boxed(), self.as_mut_raw(), world)
+            });
+        }
+    }
+
+    /// The task that finishes when the promise is resolved, like
+    /// `IO.Promise.result?`
+    ///
+    /// The task finishes with `None` if the promise is dropped without being
+    /// resolved.
+    pub fn result(&self) -> LeanTask<'r, Option<T>> {
+        unsafe {
+            let promise = self.as_mut_raw();
+            lean_inc(promise);
+            LeanTask::new(lean_io_promise_result_opt(promise))
+        }
+    }
+}
+
+impl<'r, T: LeanValue> Object<'r, PromiseTypeTag<T>> {
+    /// Creates a promise that has not been resolved yet, like
+    /// `IO.Promise.new`
+    pub fn new_promise<R: Minimal, M: Modules>(_runtime: &'r Runtime<R, M>) -> Self {
+        unsafe {
+            IoResult::run(|world| lean_io_promise_new(world))
+                .into_result::<Self, ()>()
+                .expect("`IO.Promise.new` does not fail")
+        }
+    }
+
+    pub fn resolve(&self, value: T) {
+        <Self as Borrow<Obj<_>>>::borrow(self).resolve(value)
+    }
+
+    pub fn result(&self) -> LeanTask<'r, Option<T>> {
+        <Self as Borrow<Obj<_>>>::borrow(self).result()
+    }
+}
@@ -1,19 +1,56 @@
 use std::borrow::Borrow;
+use std::cmp::Ordering;
 use std::ffi::CStr;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::Chars;
 
-use lean_sys::{lean_mk_string, lean_string_cstr};
+use lean_sys::{
+    lean_mk_string, lean_mk_string_from_bytes, lean_string_cstr, lean_string_eq, lean_string_hash,
+    lean_string_len, lean_string_lt, lean_string_size,
+};
 
 use super::{
     Owner, Reference,
+    any::{AnyObj, CheckedTypeTag, LeanTypeTag, ObjectKind},
     object::{Obj, Object},
 };
 use crate::{Minimal, Modules, Runtime};
 
+/// The type tag of Lean's `String`
+///
+/// Lean strings are UTF-8 encoded and may contain NUL characters. They are
+/// stored with a terminating NUL byte, which is not part of the string.
 pub enum StringTypeTag {}
 
-pub type LeanStr = Obj<StringTypeTag>;
+impl LeanTypeTag for StringTypeTag {
+    fn accepts_kind(kind: ObjectKind) -> bool {
+        kind == ObjectKind::String
+    }
+}
+
+unsafe impl CheckedTypeTag for StringTypeTag {
+    fn matches(object: &AnyObj<'_>) -> bool {
+        Self::accepts_kind(object.object_kind())
+    }
+}
+
+pub type LeanStr<'r> = Obj<'r, StringTypeTag>;
 
-impl Obj<StringTypeTag> {
+impl Obj<'_, StringTypeTag> {
+    /// Views the string as a `&str` without copying or validating it
+    pub fn as_str(&self) -> &str {
+        unsafe {
+            let string = self.as_mut_raw();
+            let bytes = std::slice::from_raw_parts(
+                lean_string_cstr(string).cast::<u8>(),
+                lean_string_size(string) - 1,
+            );
+            str::from_utf8_unchecked(bytes)
+        }
+    }
+
+    /// Views the string as a `&CStr`, which ends at the first NUL character
     pub fn as_cstr(&self) -> &CStr {
         unsafe {
             let string = self.as_mut_raw();
@@ -21,13 +58,38 @@ impl Obj<StringTypeTag> {
             CStr::from_ptr(string_cstring)
         }
     }
+
+    /// The length of the string in bytes
+    pub fn len(&self) -> usize {
+        unsafe { lean_string_size(self.as_mut_raw()) - 1 }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of Unicode scalar values in the string, which Lean caches
+    pub fn char_count(&self) -> usize {
+        unsafe { lean_string_len(self.as_mut_raw()) }
+    }
+
+    pub fn chars(&self) -> Chars<'_> {
+        self.as_str().chars()
+    }
 }
 
-pub type LeanString = Object<StringTypeTag>;
+pub type LeanString<'r> = Object<'r, StringTypeTag>;
+
+impl<'r> Object<'r, StringTypeTag> {
+    /// Copies a Rust string, which may contain NUL characters, into a new Lean
+    /// string
+    pub fn from_str<R: Minimal, M: Modules>(_runtime: &'r Runtime<R, M>, value: &str) -> Self {
+        let object = unsafe { lean_mk_string_from_bytes(value.as_ptr().cast(), value.len()) };
+        unsafe { Self::new(object) }
+    }
 
-impl Object<StringTypeTag> {
     pub fn from_cstr<R: Minimal, M: Modules, T: AsRef<CStr>>(
-        _runtime: &Runtime<R, M>,
+        _runtime: &'r Runtime<R, M>,
         value: T,
     ) -> Self {
         let cstr = value.as_ref();
@@ -36,7 +98,176 @@ impl Object<StringTypeTag> {
         unsafe { Self::new(object) }
     }
 
+    pub fn as_str(&self) -> &str {
+        <Self as Borrow<Obj<_>>>::borrow(self).as_str()
+    }
+
     pub fn as_cstr(&self) -> &CStr {
         <Self as Borrow<Obj<_>>>::borrow(self).as_cstr()
     }
+
+    pub fn len(&self) -> usize {
+        <Self as Borrow<Obj<_>>>::borrow(self).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        <Self as Borrow<Obj<_>>>::borrow(self).is_empty()
+    }
+
+    pub fn char_count(&self) -> usize {
+        <Self as Borrow<Obj<_>>>::borrow(self).char_count()
+    }
+
+    pub fn chars(&self) -> Chars<'_> {
+        <Self as Borrow<Obj<_>>>::borrow(self).chars()
+    }
+}
+
+impl AsRef<str> for Obj<'_, StringTypeTag> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for Object<'_, StringTypeTag> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<'a> From<&'a Obj<'_, StringTypeTag>> for &'a str {
+    fn from(string: &'a Obj<'_, StringTypeTag>) -> Self {
+        string.as_str()
+    }
+}
+
+impl From<&Obj<'_, StringTypeTag>> for String {
+    fn from(string: &Obj<'_, StringTypeTag>) -> Self {
+        string.as_str().to_owned()
+    }
+}
+
+impl From<&Object<'_, StringTypeTag>> for String {
+    fn from(string: &Object<'_, StringTypeTag>) -> Self {
+        string.as_str().to_owned()
+    }
+}
+
+impl From<Object<'_, StringTypeTag>> for String {
+    fn from(string: Object<'_, StringTypeTag>) -> Self {
+        string.as_str().to_owned()
+    }
+}
+
+/// Compares strings as Lean's `DecidableEq String` instance does
+impl PartialEq for Obj<'_, StringTypeTag> {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { lean_string_eq(self.as_mut_raw(), other.as_mut_raw()) }
+    }
+}
+
+impl Eq for Obj<'_, StringTypeTag> {}
+
+impl PartialOrd for Obj<'_, StringTypeTag> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Orders strings as Lean's `Ord String` instance does, which is
+/// lexicographic by Unicode scalar values
+impl Ord for Obj<'_, StringTypeTag> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        unsafe {
+            let (a, b) = (self.as_mut_raw(), other.as_mut_raw());
+            if lean_string_eq(a, b) {
+                Ordering::Equal
+            } else if lean_string_lt(a, b) {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        }
+    }
+}
+
+/// Feeds the hash of Lean's `Hashable String` instance to the hasher
+impl Hash for Obj<'_, StringTypeTag> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(unsafe { lean_string_hash(self.as_mut_raw()) });
+    }
+}
+
+impl PartialEq for Object<'_, StringTypeTag> {
+    fn eq(&self, other: &Self) -> bool {
+        <Self as Borrow<Obj<_>>>::borrow(self) == <Self as Borrow<Obj<_>>>::borrow(other)
+    }
+}
+
+impl Eq for Object<'_, StringTypeTag> {}
+
+impl PartialOrd for Object<'_, StringTypeTag> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Object<'_, StringTypeTag> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        <Self as Borrow<Obj<_>>>::borrow(self).cmp(<Self as Borrow<Obj<_>>>::borrow(other))
+    }
+}
+
+impl Hash for Object<'_, StringTypeTag> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        <Self as Borrow<Obj<_>>>::borrow(self).hash(state);
+    }
+}
+
+impl PartialEq<str> for Obj<'_, StringTypeTag> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Obj<'_, StringTypeTag> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<str> for Object<'_, StringTypeTag> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Object<'_, StringTypeTag> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Display for Obj<'_, StringTypeTag> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Debug for Obj<'_, StringTypeTag> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for Object<'_, StringTypeTag> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(<Self as Borrow<Obj<_>>>::borrow(self), f)
+    }
+}
+
+impl fmt::Debug for Object<'_, StringTypeTag> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(<Self as Borrow<Obj<_>>>::borrow(self), f)
+    }
 }
@@ -0,0 +1,158 @@
+use std::borrow::Borrow;
+use std::marker::PhantomData;
+
+use lean_sys::{
+    b_lean_obj_arg, lean_alloc_ctor, lean_ctor_get, lean_ctor_set, lean_dec, lean_inc,
+    lean_obj_arg, lean_obj_tag, lean_object,
+};
+
+use super::{
+    Owner, Reference,
+    any::{AnyObj, CheckedTypeTag, LeanTypeTag, ObjectKind},
+    array::{CheckedArrayTypeTag, LeanArrayTypeTag},
+    object::{Obj, Object},
+};
+use crate::{Minimal, Modules, Runtime};
+
+/// A Rust equivalent of Lean's `Sum α β`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Sum<A, B> {
+    Inl(A),
+    Inr(B),
+}
+
+impl<A, B> Sum<A, B> {
+    pub fn as_ref(&self) -> Sum<&A, &B> {
+        match self {
+            Self::Inl(value) => Sum::Inl(value),
+            Self::Inr(value) => Sum::Inr(value),
+        }
+    }
+
+    pub fn map<C, D, F: FnOnce(A) -> C, G: FnOnce(B) -> D>(self, f: F, g: G) -> Sum<C, D> {
+        match self {
+            Self::Inl(value) => Sum::Inl(f(value)),
+            Self::Inr(value) => Sum::Inr(g(value)),
+        }
+    }
+}
+
+/// The type tag of Lean's `Sum α β`, where `A` and `B` are the type tags of `α`
+/// and `β`, respectively
+///
+/// Both constructors are represented as constructor objects with one field.
+/// `Sum` can itself be used as an element type.
+pub struct SumTypeTag<A: LeanArrayTypeTag, B: LeanArrayTypeTag>(PhantomData<(A, B)>);
+
+impl<A: LeanArrayTypeTag, B: LeanArrayTypeTag> SumTypeTag<A, B> {
+    const INL_TAG: u32 = 0;
+    const INR_TAG: u32 = 1;
+}
+
+unsafe impl<A: LeanArrayTypeTag, B: LeanArrayTypeTag> LeanArrayTypeTag for SumTypeTag<A, B> {
+    type Input<'r> = Sum<A::Input<'r>, B::Input<'r>>;
+    type Output<'a, 'r>
+        = Sum<A::Output<'a, 'r>, B::Output<'a, 'r>>
+    where
+        Self: 'a,
+        'r: 'a;
+
+    fn into_element(input: Self::Input<'_>) -> *mut lean_object {
+        let (tag, value) = match input {
+            Sum::Inl(value) => (Self::INL_TAG, A::into_element(value)),
+            Sum::Inr(value) => (Self::INR_TAG, B::into_element(value)),
+        };
+        unsafe {
+            let sum = lean_alloc_ctor(tag, 1, 0);
+            lean_ctor_set(sum, 0, value);
+            sum
+        }
+    }
+
+    unsafe fn from_element<'a, 'r>(element: b_lean_obj_arg) -> Self::Output<'a, 'r>
+    where
+        Self: 'a,
+        'r: 'a,
+    {
+        unsafe {
+            let value = lean_ctor_get(element, 0);
+            if lean_obj_tag(element) == Self::INL_TAG {
+                Sum::Inl(A::from_element(value))
+            } else {
+                Sum::Inr(B::from_element(value))
+            }
+        }
+    }
+
+    unsafe fn take_element<'r>(element: lean_obj_arg) -> Self::Input<'r> {
+        unsafe {
+            let value = lean_ctor_get(element, 0);
+            let tag = lean_obj_tag(element);
+            lean_inc(value);
+            lean_dec(element);
+            if tag == Self::INL_TAG {
+                Sum::Inl(A::take_element(value))
+            } else {
+                Sum::Inr(B::take_element(value))
+            }
+        }
+    }
+}
+
+impl<A: LeanArrayTypeTag, B: LeanArrayTypeTag> LeanTypeTag for SumTypeTag<A, B> {
+    fn accepts_kind(kind: ObjectKind) -> bool {
+        matches!(
+            kind,
+            ObjectKind::Ctor { tag, num_objs: 1 } if tag == Self::INL_TAG || tag == Self::INR_TAG
+        )
+    }
+}
+
+unsafe impl<A: CheckedArrayTypeTag, B: CheckedArrayTypeTag> CheckedArrayTypeTag
+    for SumTypeTag<A, B>
+{
+    fn matches_element(element: &AnyObj<'_>) -> bool {
+        let matches_value = match element.object_kind() {
+            ObjectKind::Ctor { tag, num_objs: 1 } if tag == Self::INL_TAG => A::matches_element,
+            ObjectKind::Ctor { tag, num_objs: 1 } if tag == Self::INR_TAG => B::matches_element,
+            _ => return false,
+        };
+        matches_value(unsafe { element.ctor_field(0) })
+    }
+}
+
+unsafe impl<A: CheckedArrayTypeTag, B: CheckedArrayTypeTag> CheckedTypeTag for SumTypeTag<A, B> {
+    fn matches(object: &AnyObj<'_>) -> bool {
+        Self::matches_element(object)
+    }
+}
+
+impl<'r, A: LeanArrayTypeTag, B: LeanArrayTypeTag> Obj<'r, SumTypeTag<A, B>> {
+    pub fn get(&self) -> Sum<A::Output<'_, 'r>, B::Output<'_, 'r>> {
+        unsafe { SumTypeTag::<A, B>::from_element(self.as_mut_raw()) }
+    }
+}
+
+impl<'r, A: LeanArrayTypeTag, B: LeanArrayTypeTag> Object<'r, SumTypeTag<A, B>> {
+    pub fn from_sum<R: Minimal, M: Modules, T: Into<A::Input<'r>>, U: Into<B::Input<'r>>>(
+        _runtime: &'r Runtime<R, M>,
+        value: Sum<T, U>,
+    ) -> Self {
+        let object = SumTypeTag::<A, B>::into_element(value.map(Into::into, Into::into));
+        unsafe { Self::new(object) }
+    }
+
+    pub fn get(&self) -> Sum<A::Output<'_, 'r>, B::Output<'_, 'r>> {
+        <Self as Borrow<Obj<_>>>::borrow(self).get()
+    }
+}
+
+impl<'a, 'r, A: LeanArrayTypeTag, B: LeanArrayTypeTag> From<&'a Obj<'r, SumTypeTag<A, B>>>
+    for Sum<A::Output<'a, 'r>, B::Output<'a, 'r>>
+{
+    fn from(value: &'a Obj<'r, SumTypeTag<A, B>>) -> Self {
+        value.get()
+    }
+}
+
+pub type LeanSum<'r, A, B> = Object<'r, SumTypeTag<A, B>>;
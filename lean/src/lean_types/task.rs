@@ -0,0 +1,297 @@
+use std::borrow::Borrow;
+use std::future::{Future, IntoFuture};
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
+
+use lean_sys::{
+    b_lean_obj_arg, lean_dec, lean_inc, lean_io_cancel_core, lean_io_get_task_state_core,
+    lean_task_get, lean_task_get_own, lean_task_map_core, lean_task_pure,
+};
+
+use super::{
+    LeanValue, Owner, Reference,
+    any::{AnyObject, AnyTypeTag, LeanTypeTag, ObjectKind},
+    closure::alloc_rust_closure,
+    object::{Obj, Object, SharedObject},
+};
+use crate::{Minimal, Modules, Runtime, runtime::liveness};
+
+/// The type tag of Lean's `Task α`, where `T` is the Rust type of `α`
+///
+/// Tasks are run by Lean's task manager, which is started when the runtime is
+/// initialized. Their results are boxed, like the arguments and results of
+/// closures.
+pub struct TaskTypeTag<T: LeanValue>(PhantomData<fn() -> T>);
+
+pub type Task<'r, T> = Obj<'r, TaskTypeTag<T>>;
+pub type LeanTask<'r, T> = Object<'r, TaskTypeTag<T>>;
+
+impl<T: LeanValue> LeanTypeTag for TaskTypeTag<T> {
+    fn accepts_kind(kind: ObjectKind) -> bool {
+        kind == ObjectKind::Task
+    }
+}
+
+/// The priority of a task, as given by Lean's `Task.Priority`
+///
+/// Tasks with higher priorities are scheduled first, and dedicated tasks run
+/// on their own thread instead of occupying a thread of the task manager's
+/// pool, which suits long-running or blocking work.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TaskPriority(u32);
+
+impl TaskPriority {
+    /// `Task.Priority.default`
+    pub const DEFAULT: Self = Self(0);
+    /// `Task.Priority.max`, the highest priority of pooled tasks
+    pub const MAX: Self = Self(8);
+    /// `Task.Priority.dedicated`
+    pub const DEDICATED: Self = Self(9);
+
+    /// Creates the priority of a pooled task, or returns `None` if `priority`
+    /// exceeds [`TaskPriority::MAX`]
+    pub const fn new(priority: u32) -> Option<Self> {
+        if priority <= Self::MAX.0 {
+            Some(Self(priority))
+        } else {
+            None
+        }
+    }
+
+    pub const fn get(self) -> u32 {
+        self.0
+    }
+}
+
+/// The state of a task, as given by Lean's `IO.TaskState`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TaskState {
+    /// The task is waiting to be run, or waiting for the tasks it depends on
+    Waiting,
+    Running,
+    Finished,
+}
+
+/// # Safety
+///
+/// `task` must point to a task object.
+unsafe fn task_state(task: b_lean_obj_arg) -> TaskState {
+    match unsafe { lean_io_get_task_state_core(task) } {
+        0 => TaskState::Waiting,
+        1 => TaskState::Running,
+        _ => TaskState::Finished,
+    }
+}
+
+impl<T: LeanValue> Obj<'_, TaskTypeTag<T>> {
+    /// Blocks the current thread until the task has finished and returns a copy
+    /// of its result
+    pub fn get(&self) -> T {
+        unsafe {
+            let value = lean_task_get(self.as_mut_raw());
+            lean_inc(value);
+            T::from_boxed(value)
+        }
+    }
+
+    pub fn state(&self) -> TaskState {
+        unsafe { task_state(self.as_mut_raw()) }
+    }
+
+    /// Whether the task has finished, in which case [`Obj::get()`] does not
+    /// block
+    pub fn has_finished(&self) -> bool {
+        self.state() == TaskState::Finished
+    }
+
+    /// Requests the cancellation of the task, like `IO.cancel`
+    ///
+    /// Cancellation is cooperative: the task keeps running until its code
+    /// checks for cancellation with `IO.checkCanceled`, or with
+    /// [`Runtime::check_canceled()`] in Rust, and stops early.
+    pub fn cancel(&self) {
+        unsafe { lean_io_cancel_core(self.as_mut_raw()) }
+    }
+}
+
+impl<'r, T: LeanValue> Object<'r, TaskTypeTag<T>> {
+    /// Creates a task that has already finished with `value`, like `Task.pure`
+    pub fn pure<R: Minimal, M: Modules>(_runtime: &'r Runtime<R, M>, value: T) -> Self {
+        unsafe { Self::new(lean_task_pure(value.into_boxed())) }
+    }
+
+    /// Blocks the current thread until the task has finished and returns its
+    /// result
+    ///
+    /// To wait without blocking, await the task instead.
+    pub fn get(self) -> T {
+        unsafe { T::from_boxed(lean_task_get_own(self.into_raw())) }
+    }
+
+    pub fn state(&self) -> TaskState {
+        <Self as Borrow<Obj<_>>>::borrow(self).state()
+    }
+
+    pub fn has_finished(&self) -> bool {
+        <Self as Borrow<Obj<_>>>::borrow(self).has_finished()
+    }
+
+    pub fn cancel(&self) {
+        <Self as Borrow<Obj<_>>>::borrow(self).cancel()
+    }
+}
+
+/// Tasks are awaited through [`TaskFuture`], which keeps track of the waker to
+/// notify when the task finishes
+impl<'r, T: LeanValue> IntoFuture for Object<'r, TaskTypeTag<T>> {
+    type Output = T;
+    type IntoFuture = TaskFuture<'r, T>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        TaskFuture {
+            task: self,
+            completion: None,
+        }
+    }
+}
+
+/// A future that resolves to the result of a Lean task
+///
+/// The first time the future is polled before the task has finished, a
+/// continuation is attached to the task with `lean_task_map_core()`, which
+/// wakes the executor from the thread that finishes the task, so the task is
+/// never polled in a loop nor waited for by a blocked thread.
+pub struct TaskFuture<'r, T: LeanValue> {
+    task: Object<'r, TaskTypeTag<T>>,
+    completion: Option<Arc<Mutex<Completion>>>,
+}
+
+/// The state that a task's continuation shares with its future
+struct Completion {
+    finished: bool,
+    waker: Option<Waker>,
+}
+
+impl<T: LeanValue> TaskFuture<'_, T> {
+    fn task(&self) -> &Obj<'_, TaskTypeTag<T>> {
+        <Object<_> as Borrow<Obj<_>>>::borrow(&self.task)
+    }
+
+    /// Attaches a continuation to the task that wakes `waker` when it finishes
+    fn register(&self, waker: Waker) -> Arc<Mutex<Completion>> {
+        let completion = Arc::new(Mutex::new(Completion {
+            finished: false,
+            waker: Some(waker),
+        }));
+        let continuation_completion = Arc::clone(&completion);
+        // The continuation receives the result of the task, which it drops
+        let continuation = alloc_rust_closure(move |_result: AnyObject<'static>| {
+            let waker = {
+                let mut completion = continuation_completion
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner);
+                completion.finished = true;
+                completion.waker.take()
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        });
+        unsafe {
+            let task = self.task.as_mut_raw();
+            lean_inc(task);
+            // The continuation runs synchronously on the thread that finishes
+            // the task, and is kept alive although its own task is dropped
+            lean_dec(lean_task_map_core(continuation, task, 0, true, true));
+        }
+        completion
+    }
+}
+
+impl<T: LeanValue> Future for TaskFuture<'_, T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if this.task().has_finished() {
+            return Poll::Ready(this.task().get());
+        }
+        match &this.completion {
+            Some(completion) => {
+                let mut completion = completion.lock().unwrap_or_else(PoisonError::into_inner);
+                if completion.finished {
+                    return Poll::Ready(this.task().get());
+                }
+                completion.waker = Some(cx.waker().clone());
+            }
+            None => this.completion = Some(this.register(cx.waker().clone())),
+        }
+        Poll::Pending
+    }
+}
+
+/// A handle to cancel Lean tasks started from Rust, for example when the
+/// client that is waiting for their results disconnects
+///
+/// Tokens can be cloned and sent to other threads, and cancelling them does
+/// not need a runtime, so any thread can cancel the tasks attached to a token.
+/// Tasks that are attached after the token is cancelled are cancelled
+/// immediately.
+#[derive(Clone, Default)]
+pub struct CancellationToken(Arc<Mutex<Cancellation>>);
+
+#[derive(Default)]
+struct Cancellation {
+    canceled: bool,
+    tasks: Vec<SharedObject<AnyTypeTag>>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Cancellation> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Cancels `task` when this token is cancelled
+    ///
+    /// The token keeps the task alive until it finishes or the token is
+    /// dropped.
+    pub fn attach<'r, T: LeanValue>(&self, task: &impl Borrow<Obj<'r, TaskTypeTag<T>>>) {
+        let task = task.borrow();
+        let mut cancellation = self.lock();
+        if cancellation.canceled {
+            task.cancel();
+            return;
+        }
+        cancellation
+            .tasks
+            .retain(|task| unsafe { task_state(task.as_mut_raw()) } != TaskState::Finished);
+        cancellation
+            .tasks
+            .push(task.as_any().to_owned().into_shared());
+    }
+
+    /// Cancels the attached tasks, see [`Obj::cancel()`]
+    pub fn cancel(&self) {
+        let tasks = {
+            let mut cancellation = self.lock();
+            cancellation.canceled = true;
+            std::mem::take(&mut cancellation.tasks)
+        };
+        // There is nothing left to cancel once the runtime is finalized
+        liveness::with_live_runtime(|| {
+            for task in &tasks {
+                unsafe { lean_io_cancel_core(task.as_mut_raw()) };
+            }
+        });
+    }
+
+    pub fn is_canceled(&self) -> bool {
+        self.lock().canceled
+    }
+}
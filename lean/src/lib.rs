@@ -14,6 +14,7 @@ pub use lean_macro::*;
 
 mod alloc;
 mod error;
+mod io;
 pub mod lean_types;
 mod module;
 mod runtime;
@@ -23,7 +24,7 @@ mod thread;
 use module::ModulesInitializer;
 
 pub use alloc::MimallocAllocator;
-pub use error::{LeanError, LeanIoError};
+pub use error::{LeanError, LeanIoError, LeanIoErrorKind};
 pub use module::NoModules;
 pub use runtime::{
     ArgcError, LeanPackage, LeanPackageComponents, Minimal, MinimalComponents, Runtime,
@@ -1,11 +1,8 @@
 use std::marker::PhantomData;
 
-use lean_sys::{
-    lean_box, lean_dec, lean_io_mk_world, lean_io_result_is_ok, lean_io_result_mk_ok, lean_obj_arg,
-    lean_obj_res, lean_object,
-};
+use lean_sys::{lean_box, lean_io_result_mk_ok, lean_obj_arg, lean_obj_res};
 
-use crate::{Modules, Runtime, RuntimeComponents, sync::NonSendNonSync};
+use crate::{Modules, Runtime, RuntimeComponents, io::IoResult, sync::NonSendNonSync};
 
 pub enum NoModules {}
 
@@ -31,19 +28,15 @@ impl<R: RuntimeComponents, M: Modules> ModulesInitializer<R, M> {
     }
 
     pub fn new() -> Result<Self, lean_obj_res> {
-        let res: *mut lean_object;
         // Use same default as for Lean executables
         // See <https://github.com/leanprover/lean4/blob/master/doc/dev/ffi.md#initialization>
         let builtin: u8 = 1;
 
-        unsafe {
-            res = M::initialize_modules(builtin, lean_io_mk_world());
-            if lean_io_result_is_ok(res) {
-                lean_dec(res);
-                Ok(Self::initialize_fields())
-            } else {
-                Err(res)
-            }
+        let result = unsafe { IoResult::run(|world| M::initialize_modules(builtin, world)) };
+        if result.is_ok() {
+            Ok(Self::initialize_fields())
+        } else {
+            Err(result.into_raw())
         }
     }
 
@@ -8,6 +8,7 @@ use crate::{LeanError, LeanIoError, Modules, RuntimeComponents};
 mod components;
 mod handle;
 mod initialization;
+pub(crate) mod liveness;
 
 pub use components::{
     ArgcError, LeanPackage, LeanPackageComponents, Minimal, MinimalComponents,
@@ -1,6 +1,21 @@
 use std::marker::PhantomData;
 
-use crate::{Modules, RuntimeComponents, sync::NonSendNonSync};
+use lean_sys::{lean_io_check_canceled_core, lean_obj_arg, lean_obj_res, lean_task_spawn_core};
+
+use crate::{
+    Minimal, Modules, RuntimeComponents,
+    io::IoResult,
+    lean_types::{
+        LeanValue, Owner,
+        closure::{RustFnMut, alloc_rust_closure},
+        io_error::IoErrorTypeTag,
+        object::Object,
+        task::{LeanTask, TaskPriority},
+    },
+    sync::NonSendNonSync,
+};
+
+use super::liveness;
 
 pub struct Runtime<R: RuntimeComponents, M: Modules> {
     runtime_components: PhantomData<R>,
@@ -11,6 +26,7 @@ pub struct Runtime<R: RuntimeComponents, M: Modules> {
 
 impl<R: RuntimeComponents, M: Modules> Runtime<R, M> {
     fn new(is_main_thread: bool) -> Self {
+        liveness::enter_thread();
         Self {
             runtime_components: PhantomData,
             modules_initializer: PhantomData,
@@ -20,20 +36,96 @@ impl<R: RuntimeComponents, M: Modules> Runtime<R, M> {
     }
 
     pub(crate) fn new_main_thread() -> Self {
+        liveness::initialize();
         Self::new(true)
     }
 
     pub(crate) fn new_secondary_thread() -> Self {
         Self::new(false)
     }
+
+    /// Runs a Lean function of type `EIO ε α`, such as an `@[export]`ed Lean
+    /// function
+    ///
+    /// `io_action` receives a new world token, which must be passed as the last
+    /// argument of the Lean function. The error and value of the IO result are
+    /// converted to `E` and `T`, respectively.
+    ///
+    /// # Safety
+    ///
+    /// `io_action` must return an owned `EIO` result whose error and value have
+    /// types that correspond to `E` and `T`, respectively. As required by
+    /// [`LeanValue::from_boxed()`], `E` and `T` must not be branded with a
+    /// lifetime that outlives `self`, such as `'static`, since the objects
+    /// that they hold belong to this runtime.
+    pub unsafe fn run_eio<E: LeanValue, T: LeanValue, F: FnOnce(lean_obj_arg) -> lean_obj_res>(
+        &self,
+        io_action: F,
+    ) -> Result<T, E> {
+        unsafe { IoResult::run(io_action).into_result() }
+    }
+
+    /// Runs a Lean function of type `IO α`, such as an `@[export]`ed Lean
+    /// function
+    ///
+    /// See [`Runtime::run_eio()`]. The error remains a Lean object, so it can
+    /// be re-raised, and it converts to [`LeanIoError`](crate::LeanIoError).
+    ///
+    /// # Safety
+    ///
+    /// `io_action` must return an owned `IO` result whose value has a type that
+    /// corresponds to `T`, which must not be branded with a lifetime that
+    /// outlives `self`, as for [`Runtime::run_eio()`].
+    pub unsafe fn run_io<T: LeanValue, F: FnOnce(lean_obj_arg) -> lean_obj_res>(
+        &self,
+        io_action: F,
+    ) -> Result<T, Object<'_, IoErrorTypeTag>> {
+        unsafe { self.run_eio(io_action) }
+    }
+}
+
+impl<R: Minimal, M: Modules> Runtime<R, M> {
+    /// Runs a Rust closure as a Lean task on Lean's task manager, like
+    /// `Task.spawn`
+    ///
+    /// The closure receives a runtime for the thread of the task manager that
+    /// runs it. Lean objects that it returns must be detached, as they cannot
+    /// be branded with a runtime of the current thread. Like any other task,
+    /// the returned task can be awaited from Rust or passed to Lean code.
+    pub fn spawn_task<T: LeanValue + 'static, F: FnOnce(&Runtime<R, M>) -> T + Send + 'static>(
+        &self,
+        f: F,
+        priority: TaskPriority,
+    ) -> LeanTask<'_, T> {
+        let mut f = Some(f);
+        // `Task.spawn` takes a closure of type `Unit → α`, which Lean calls
+        // once
+        let closure = alloc_rust_closure(RustFnMut::new(move |(): ()| {
+            let f = f.take().expect("task closure called more than once");
+            f(&Runtime::new_secondary_thread())
+        }));
+        unsafe { LeanTask::new(lean_task_spawn_core(closure, priority.get(), false)) }
+    }
+
+    /// Whether the task that runs the current code has been cancelled, like
+    /// `IO.checkCanceled`
+    ///
+    /// Long-running closures passed to [`Runtime::spawn_task()`] should check
+    /// this regularly and return early once their task is cancelled. Outside
+    /// of tasks, this is always `false`.
+    pub fn check_canceled(&self) -> bool {
+        unsafe { lean_io_check_canceled_core() }
+    }
 }
 
 impl<R: RuntimeComponents, M: Modules> Drop for Runtime<R, M> {
     fn drop(&mut self) {
         if self.is_main_thread {
             unsafe {
+                liveness::finalize();
                 R::finalize_runtime();
             }
         }
+        liveness::exit_thread();
     }
 }
@@ -0,0 +1,119 @@
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::thread;
+
+use lean_sys::{lean_dec, lean_object};
+
+/// Whether the runtime is initialized and has not been finalized yet
+static IS_LIVE: AtomicBool = AtomicBool::new(false);
+
+/// The number of threads that are using the runtime through
+/// [`with_live_runtime()`], which the finalization of the runtime waits for
+static USERS: AtomicUsize = AtomicUsize::new(0);
+
+/// Objects that were released on threads without a runtime, which are freed
+/// on the next thread that has one
+static PENDING_RELEASES: Mutex<Vec<PendingRelease>> = Mutex::new(Vec::new());
+static HAS_PENDING_RELEASES: AtomicBool = AtomicBool::new(false);
+
+struct PendingRelease(*mut lean_object);
+
+// Pending objects are multi-threaded or persistent
+unsafe impl Send for PendingRelease {}
+
+thread_local! {
+    /// The number of runtimes of the current thread
+    static THREAD_RUNTIMES: Cell<usize> = const { Cell::new(0) };
+}
+
+pub(crate) fn enter_thread() {
+    THREAD_RUNTIMES.set(THREAD_RUNTIMES.get() + 1);
+}
+
+pub(crate) fn exit_thread() {
+    THREAD_RUNTIMES.set(THREAD_RUNTIMES.get() - 1);
+}
+
+fn has_thread_runtime() -> bool {
+    THREAD_RUNTIMES.get() > 0
+}
+
+pub(crate) fn initialize() {
+    IS_LIVE.store(true, Ordering::SeqCst);
+}
+
+/// Marks the runtime as finalized, after waiting for the threads that are
+/// using it and freeing the objects whose release is pending
+///
+/// Objects that are released afterwards are leaked.
+///
+/// # Safety
+///
+/// Must be called on the main thread of the runtime, before it is finalized.
+pub(crate) unsafe fn finalize() {
+    unsafe { release_pending() };
+    IS_LIVE.store(false, Ordering::SeqCst);
+    while USERS.load(Ordering::SeqCst) > 0 {
+        thread::yield_now();
+    }
+    unsafe { release_pending() };
+}
+
+/// Runs `f` unless the runtime has been finalized, which it cannot be until
+/// `f` returns
+///
+/// This lets objects that are not branded with a runtime, such as
+/// [`SharedObject`](crate::lean_types::object::SharedObject), be used from any
+/// thread.
+pub(crate) fn with_live_runtime<T>(f: impl FnOnce() -> T) -> Option<T> {
+    USERS.fetch_add(1, Ordering::SeqCst);
+    let output = IS_LIVE.load(Ordering::SeqCst).then(f);
+    USERS.fetch_sub(1, Ordering::SeqCst);
+    output
+}
+
+/// Releases a reference to a multi-threaded or persistent object that is not
+/// branded with a runtime
+///
+/// The object is released at once on threads that have a runtime. Elsewhere,
+/// it is released later by a thread that has one, since freeing objects
+/// requires a thread that is initialized for Lean. The object is leaked if the
+/// runtime has been finalized.
+///
+/// # Safety
+///
+/// `object` must be an owned multi-threaded or persistent object.
+pub(crate) unsafe fn release(object: *mut lean_object) {
+    with_live_runtime(|| {
+        if has_thread_runtime() {
+            unsafe { lean_dec(object) };
+            if HAS_PENDING_RELEASES.load(Ordering::SeqCst) {
+                unsafe { release_pending() };
+            }
+        } else {
+            let mut pending = PENDING_RELEASES
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            pending.push(PendingRelease(object));
+            HAS_PENDING_RELEASES.store(true, Ordering::SeqCst);
+        }
+    });
+}
+
+/// # Safety
+///
+/// Must be called on a thread that has a runtime, which has not been finalized.
+unsafe fn release_pending() {
+    let pending = {
+        let mut pending = PENDING_RELEASES
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        HAS_PENDING_RELEASES.store(false, Ordering::SeqCst);
+        std::mem::take(&mut *pending)
+    };
+    // Freeing objects may release other objects, so the lock is not held
+    for PendingRelease(object) in pending {
+        unsafe { lean_dec(object) };
+    }
+}
@@ -0,0 +1,126 @@
+use std::convert::Infallible;
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{
+        any::{AnyTypeTag, ObjectKind},
+        array::{
+            F64ArrayTypeTag, ObjectArray, ObjectArrayTypeTag, U8ArrayTypeTag, U32Array,
+            U32ArrayTypeTag, U64ArrayTypeTag,
+        },
+        byte_array::{ByteArray, ByteArrayTypeTag},
+        ctor::{CtorBuilder, CtorLayout, LeanCtorTypeTag, ScalarField},
+        nat::{LeanNat, NatTypeTag},
+        option::OptionTypeTag,
+        prod::{LeanProd, ProdTypeTag},
+        string::{LeanString, StringTypeTag},
+    },
+};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+/// ```lean
+/// structure Span where
+///   start : UInt64
+///   stop : UInt64
+/// ```
+enum SpanTypeTag {}
+
+unsafe impl LeanCtorTypeTag for SpanTypeTag {
+    const LAYOUTS: &'static [CtorLayout] = &[CtorLayout::new(0, 0, 16)];
+}
+
+const START: ScalarField<u64> = unsafe { ScalarField::new(0) };
+const STOP: ScalarField<u64> = unsafe { ScalarField::new(8) };
+
+#[test]
+fn any_object() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let string = LeanString::from_str(runtime, "payload").into_any();
+            assert_eq!(string.object_kind(), ObjectKind::String);
+            assert!(string.is::<StringTypeTag>());
+            assert!(string.is::<AnyTypeTag>());
+            assert!(!string.is::<NatTypeTag>());
+            assert_eq!(string.downcast_ref::<StringTypeTag>().unwrap(), "payload");
+            let string = string.downcast::<NatTypeTag>().unwrap_err();
+            assert_eq!(string.downcast::<StringTypeTag>().ok().unwrap(), "payload");
+
+            let small = LeanNat::from_u64(runtime, 42).into_any();
+            assert_eq!(small.object_kind(), ObjectKind::Scalar(42));
+            let large = LeanNat::from_u64(runtime, u64::MAX).into_any();
+            assert_eq!(large.object_kind(), ObjectKind::Mpz);
+            assert_eq!(
+                large.downcast::<NatTypeTag>().ok().unwrap().to_u64(),
+                Some(u64::MAX)
+            );
+
+            let bytes = ByteArray::from_exact_size_iterator(runtime, [1, 2, 3]).into_any();
+            assert_eq!(
+                bytes.object_kind(),
+                ObjectKind::ScalarArray { elem_size: 1 }
+            );
+            assert!(bytes.is::<ByteArrayTypeTag>());
+            assert!(!bytes.is::<U8ArrayTypeTag>());
+
+            // Arrays of boxed scalars are checked element-wise
+            let u32s = U32Array::from_exact_size_iterator(runtime, [1, 2, u32::MAX]).into_any();
+            assert_eq!(u32s.object_kind(), ObjectKind::Array);
+            assert!(!u32s.is::<ByteArrayTypeTag>());
+            assert!(!u32s.is::<U8ArrayTypeTag>());
+            assert!(!u32s.is::<U64ArrayTypeTag>());
+            assert!(u32s.is::<U32ArrayTypeTag>());
+
+            // Constructors without boxed fields are only boxed scalars if their
+            // scalar field has the size of the element type
+            let spans = ObjectArray::<SpanTypeTag>::from_exact_size_iterator(
+                runtime,
+                [(0, 4), (4, 8)].map(|(start, stop)| {
+                    let mut builder = CtorBuilder::<SpanTypeTag>::new(runtime, 0);
+                    builder.set_scalar(START, start).set_scalar(STOP, stop);
+                    builder.build()
+                }),
+            )
+            .into_any();
+            assert!(spans.is::<ObjectArrayTypeTag<AnyTypeTag>>());
+            assert!(!spans.is::<U64ArrayTypeTag>());
+            assert!(!spans.is::<F64ArrayTypeTag>());
+
+            let strings = ObjectArray::<StringTypeTag>::from_exact_size_iterator(
+                runtime,
+                [
+                    LeanString::from_str(runtime, "a"),
+                    LeanString::from_str(runtime, "b"),
+                ],
+            )
+            .into_any();
+            assert!(strings.is::<ObjectArrayTypeTag<StringTypeTag>>());
+            assert!(!strings.is::<ObjectArrayTypeTag<NatTypeTag>>());
+
+            let pair = LeanProd::<U64ArrayTypeTag, OptionTypeTag<U8ArrayTypeTag>>::from_tuple(
+                runtime,
+                (7_u64, Some(8_u8)),
+            )
+            .into_any();
+            assert_eq!(
+                pair.object_kind(),
+                ObjectKind::Ctor {
+                    tag: 0,
+                    num_objs: 2
+                }
+            );
+            assert!(pair.is::<ProdTypeTag<U64ArrayTypeTag, OptionTypeTag<U8ArrayTypeTag>>>());
+            assert!(!pair.is::<ProdTypeTag<U64ArrayTypeTag, U64ArrayTypeTag>>());
+            assert!(!pair.is::<OptionTypeTag<U8ArrayTypeTag>>());
+            assert_eq!(
+                pair.downcast_ref::<ProdTypeTag<U64ArrayTypeTag, OptionTypeTag<U8ArrayTypeTag>>>()
+                    .unwrap()
+                    .fst(),
+                7
+            );
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
@@ -0,0 +1,73 @@
+use std::convert::Infallible;
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{
+        Owner,
+        array::{U64Array, U64ArrayTypeTag},
+        byte_array::ByteArray,
+        float_array::FloatArray,
+        object::Object,
+        option::OptionTypeTag,
+    },
+};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+#[test]
+fn array_mut() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let mut array = U64Array::from_exact_size_iterator(runtime, [1_u64, 2, 3]);
+            assert!(array.is_exclusive());
+            assert!(!array.is_shared());
+            assert!(!array.is_persistent());
+
+            let mut unique = array.try_unique().unwrap();
+            unique.set(0, u64::MAX);
+            unique.push(4_u64);
+            unique.swap(1, 2);
+            assert_eq!(unique.pop(), Some(4));
+            assert_eq!(unique.len(), 3);
+            assert!(unique.iter().eq([u64::MAX, 3, 2]));
+
+            // Mutating a shared array copies it first
+            let shared = array.share();
+            assert!(array.is_shared());
+            assert!(array.try_unique().is_none());
+            array.make_mut().push(5_u64);
+            assert!(array.is_exclusive());
+            assert!(array.iter().eq([u64::MAX, 3, 2, 5]));
+            assert!(shared.iter().eq([u64::MAX, 3, 2]));
+
+            let mut options = Object::<OptionTypeTag<U64ArrayTypeTag>>::from_exact_size_iterator(
+                runtime,
+                [Some(1_u64), None],
+            );
+            let mut options_mut = options.make_mut();
+            assert_eq!(options_mut.pop(), Some(None));
+            assert_eq!(options_mut.pop(), Some(Some(1)));
+            assert_eq!(options_mut.pop(), None);
+            assert!(options_mut.is_empty());
+
+            let mut bytes = ByteArray::from_exact_size_iterator(runtime, [1_u8, 2, 3]);
+            let shared_bytes = bytes.share();
+            let mut bytes_mut = bytes.make_mut();
+            bytes_mut.as_mut_slice().reverse();
+            bytes_mut.push(0);
+            bytes_mut.set(0, 4);
+            assert_eq!(bytes_mut.pop(), Some(0));
+            assert_eq!(bytes.as_slice(), [4, 2, 1]);
+            assert_eq!(shared_bytes.as_slice(), [1, 2, 3]);
+
+            let mut floats = FloatArray::from_exact_size_iterator(runtime, [0.5]);
+            let mut floats_mut = floats.try_unique().unwrap();
+            floats_mut.push(1.5);
+            floats_mut.swap(0, 1);
+            assert_eq!(floats_mut.as_slice(), [1.5, 0.5]);
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
@@ -0,0 +1,76 @@
+use std::convert::Infallible;
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{Owner, closure::LeanFn, nat::LeanNat},
+};
+use lean_sys::{
+    lean_alloc_closure, lean_box_uint32, lean_dec, lean_nat_add, lean_obj_arg, lean_obj_res,
+    lean_unbox_uint32,
+};
+use num_bigint::BigUint;
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+/// Boxed version of `fun (a b : Nat) => a + b`
+extern "C" fn add(a: lean_obj_arg, b: lean_obj_arg) -> lean_obj_res {
+    unsafe {
+        let sum = lean_nat_add(a, b);
+        lean_dec(a);
+        lean_dec(b);
+        sum
+    }
+}
+
+/// Boxed version of `fun (x factor : UInt32) (offset : UInt32) => x * factor + offset`
+extern "C" fn scale(x: lean_obj_arg, factor: lean_obj_arg, offset: lean_obj_arg) -> lean_obj_res {
+    unsafe {
+        let result = lean_unbox_uint32(x)
+            .wrapping_mul(lean_unbox_uint32(factor))
+            .wrapping_add(lean_unbox_uint32(offset));
+        lean_box_uint32(result)
+    }
+}
+
+#[test]
+fn closure() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let add: LeanFn<(LeanNat, LeanNat), LeanNat> =
+                unsafe { LeanFn::new(lean_alloc_closure(add as *mut _, 2, 0)) };
+            assert_eq!(add.arity(), 2);
+            assert_eq!(add.num_fixed(), 0);
+
+            let sum = add.call((LeanNat::from_u64(runtime, 2), LeanNat::from_u64(runtime, 3)));
+            assert_eq!(sum.to_u64(), Some(5));
+
+            let add_ten = add.apply_first(LeanNat::from_u64(runtime, 10));
+            assert_eq!(add_ten.num_fixed(), 1);
+            assert_eq!(
+                add_ten
+                    .call((LeanNat::from_u64(runtime, u64::MAX),))
+                    .to_biguint(),
+                BigUint::from(u64::MAX) + 10_u32
+            );
+
+            // The partially applied closure does not consume the original one,
+            // which can also be applied to an array of arguments
+            let add_array: LeanFn<[LeanNat; 2], LeanNat> = unsafe { LeanFn::new(add.into_raw()) };
+            assert_eq!(
+                add_array
+                    .call([LeanNat::from_u64(runtime, 4), LeanNat::from_u64(runtime, 5)])
+                    .to_u64(),
+                Some(9)
+            );
+
+            let scale: LeanFn<(u32, u32), LeanFn<(u32,), u32>> =
+                unsafe { LeanFn::new(lean_alloc_closure(scale as *mut _, 3, 0)) };
+            let scale_by_three = scale.call((7, 3));
+            assert_eq!(scale_by_three.call((1,)), 22);
+
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
@@ -0,0 +1,48 @@
+use std::convert::Infallible;
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{
+        CollectIntoLean,
+        array::{ArrayBuilder, U32Array, U32ArrayTypeTag},
+        byte_array::{ByteArray, ByteArrayBuilder},
+        float_array::FloatArray,
+    },
+};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+#[test]
+fn collect_into_lean() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let evens: U32Array = (0_u32..100)
+                .filter(|i| i % 2 == 0)
+                .collect_into_lean(runtime);
+            assert_eq!(evens.iter().len(), 50);
+            assert!(evens.iter().eq((0..100).step_by(2)));
+
+            let bytes: ByteArray = ["ab", "", "cde"]
+                .into_iter()
+                .flat_map(str::bytes)
+                .collect_into_lean(runtime);
+            assert_eq!(bytes.as_slice(), b"abcde");
+
+            let floats: FloatArray = std::iter::empty::<f64>().collect_into_lean(runtime);
+            assert!(floats.as_slice().is_empty());
+
+            let mut builder = ArrayBuilder::<U32ArrayTypeTag>::with_capacity(runtime, 1);
+            builder.push(1_u32).push(2_u32);
+            builder.extend([3_u32, 4]);
+            assert_eq!(builder.len(), 4);
+            assert!(builder.build().iter().eq([1, 2, 3, 4]));
+
+            let mut byte_builder = ByteArrayBuilder::new(runtime);
+            byte_builder.extend(0_u8..=255);
+            assert!(byte_builder.build().iter().eq(0..=255));
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
@@ -0,0 +1,65 @@
+use std::convert::Infallible;
+use std::ffi::CString;
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{
+        Owner,
+        ctor::{CtorBuilder, CtorLayout, LeanCtorTypeTag, ObjectField, ScalarField},
+        object::Object,
+        string::{LeanString, StringTypeTag},
+    },
+};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+/// Type tag for the Lean structure
+///
+/// ```lean
+/// structure Entry where
+///   name : String
+///   count : UInt64
+///   flag : Bool
+/// ```
+enum EntryTypeTag {}
+
+unsafe impl LeanCtorTypeTag for EntryTypeTag {
+    const LAYOUTS: &'static [CtorLayout] = &[CtorLayout::new(1, 0, 9)];
+}
+
+const NAME: ObjectField<StringTypeTag> = unsafe { ObjectField::new(0) };
+const COUNT: ScalarField<u64> = unsafe { ScalarField::new(0) };
+const FLAG: ScalarField<bool> = unsafe { ScalarField::new(8) };
+
+#[test]
+fn ctor() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let name = CString::new("entry").unwrap();
+            let mut builder = CtorBuilder::<EntryTypeTag>::new(runtime, 0);
+            builder
+                .set_object(NAME, LeanString::from_cstr(runtime, &name))
+                .set_scalar(COUNT, u64::MAX)
+                .set_scalar(FLAG, true);
+            let mut entry = builder.build();
+
+            let ctor = entry.as_ctor().unwrap();
+            assert_eq!(ctor.tag(), 0);
+            assert_eq!(ctor.object(NAME).as_cstr(), name.as_c_str());
+            assert_eq!(ctor.scalar(COUNT), u64::MAX);
+            assert!(ctor.scalar(FLAG));
+
+            entry.as_ctor_mut().unwrap().set_scalar(FLAG, false);
+            assert!(!entry.as_ctor().unwrap().scalar(FLAG));
+
+            let shared: Object<EntryTypeTag> = entry.share();
+            assert!(entry.as_ctor_mut().is_none());
+            drop(shared);
+            assert!(entry.as_ctor_mut().is_some());
+
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
@@ -0,0 +1,31 @@
+use std::convert::Infallible;
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{
+        Borrower, Reference,
+        byte_array::ByteArr,
+        string::{LeanStr, LeanString},
+    },
+};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+#[test]
+#[should_panic(
+    expected = "object of kind String cannot be wrapped as an object of type tag `lean::lean_types::byte_array::ByteArrayTypeTag`"
+)]
+fn debug_checks() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let string = LeanString::from_str(runtime, "not bytes");
+            let raw = string.as_raw().cast_mut();
+            let borrowed: &LeanStr = unsafe { Borrower::new(&raw) };
+            assert_eq!(borrowed, "not bytes");
+            let _bytes: &ByteArr = unsafe { Borrower::new(&raw) };
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
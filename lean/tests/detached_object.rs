@@ -0,0 +1,29 @@
+use std::convert::Infallible;
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime, lean_types::string::LeanString,
+};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+#[test]
+fn detached_object() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let greeting = LeanString::from_str(runtime, "hello").detach();
+            let reply = lean::run_in_thread_with_lean_runtime(
+                runtime,
+                move |runtime: &Runtime<MinimalComponents, NoModules>| {
+                    let greeting = greeting.attach(runtime);
+                    LeanString::from_str(runtime, &format!("{greeting}, world")).detach()
+                },
+            )
+            .join()
+            .unwrap();
+            assert_eq!(reply.attach(runtime), "hello, world");
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
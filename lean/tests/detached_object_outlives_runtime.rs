@@ -0,0 +1,52 @@
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::external::{ExternalObject, LeanExternal},
+};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+struct Handle {
+    _open: Arc<()>,
+}
+
+impl LeanExternal for Handle {}
+
+#[test]
+fn detached_object_outlives_runtime() {
+    let dropped_elsewhere = Arc::new(());
+    let returned = Arc::new(());
+    let handle = lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            // Threads that are not initialized for Lean leave the object to be
+            // freed by a thread that has a runtime
+            let handle = ExternalObject::from_value(
+                runtime,
+                Handle {
+                    _open: Arc::clone(&dropped_elsewhere),
+                },
+            )
+            .detach();
+            std::thread::spawn(move || drop(handle)).join().unwrap();
+
+            Ok::<_, Infallible>(
+                ExternalObject::from_value(
+                    runtime,
+                    Handle {
+                        _open: Arc::clone(&returned),
+                    },
+                )
+                .detach(),
+            )
+        },
+    )
+    .unwrap();
+    assert_eq!(Arc::strong_count(&dropped_elsewhere), 1);
+
+    // The runtime has been finalized, so the object is leaked
+    drop(handle);
+    assert_eq!(Arc::strong_count(&returned), 2);
+}
@@ -0,0 +1,114 @@
+use std::borrow::Borrow;
+use std::convert::Infallible;
+
+use lean::{
+    LeanInductive, LeanStructure, MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{
+        any::{AnyTypeTag, ObjectKind},
+        ctor::{
+            CtorLayout, FieldKind, LeanCtorTypeTag, LeanField, LeanStructure, ObjectField,
+            ScalarField,
+        },
+    },
+};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+/// A type with a single constructor, which Lean does not treat as an
+/// enumeration type
+#[derive(LeanInductive, Clone, Copy, Debug, PartialEq, Eq)]
+enum Single {
+    Only,
+}
+
+/// Declares an enumeration type, listing its variants without one line each
+macro_rules! enumeration {
+    ($(#[$attr:meta])* $name:ident { $($variant:ident)* }) => {
+        $(#[$attr])*
+        #[derive(LeanInductive, Clone, Copy, Debug, PartialEq, Eq)]
+        enum $name {
+            $($variant,)*
+        }
+    };
+}
+
+enumeration! {
+    /// An enumeration type that fits in a `UInt8`
+    Narrow { A B C }
+}
+
+enumeration! {
+    /// The smallest enumeration type that does not fit in a `UInt8`
+    Wide {
+        V000 V001 V002 V003 V004 V005 V006 V007 V008 V009 V010 V011 V012 V013 V014 V015
+        V016 V017 V018 V019 V020 V021 V022 V023 V024 V025 V026 V027 V028 V029 V030 V031
+        V032 V033 V034 V035 V036 V037 V038 V039 V040 V041 V042 V043 V044 V045 V046 V047
+        V048 V049 V050 V051 V052 V053 V054 V055 V056 V057 V058 V059 V060 V061 V062 V063
+        V064 V065 V066 V067 V068 V069 V070 V071 V072 V073 V074 V075 V076 V077 V078 V079
+        V080 V081 V082 V083 V084 V085 V086 V087 V088 V089 V090 V091 V092 V093 V094 V095
+        V096 V097 V098 V099 V100 V101 V102 V103 V104 V105 V106 V107 V108 V109 V110 V111
+        V112 V113 V114 V115 V116 V117 V118 V119 V120 V121 V122 V123 V124 V125 V126 V127
+        V128 V129 V130 V131 V132 V133 V134 V135 V136 V137 V138 V139 V140 V141 V142 V143
+        V144 V145 V146 V147 V148 V149 V150 V151 V152 V153 V154 V155 V156 V157 V158 V159
+        V160 V161 V162 V163 V164 V165 V166 V167 V168 V169 V170 V171 V172 V173 V174 V175
+        V176 V177 V178 V179 V180 V181 V182 V183 V184 V185 V186 V187 V188 V189 V190 V191
+        V192 V193 V194 V195 V196 V197 V198 V199 V200 V201 V202 V203 V204 V205 V206 V207
+        V208 V209 V210 V211 V212 V213 V214 V215 V216 V217 V218 V219 V220 V221 V222 V223
+        V224 V225 V226 V227 V228 V229 V230 V231 V232 V233 V234 V235 V236 V237 V238 V239
+        V240 V241 V242 V243 V244 V245 V246 V247 V248 V249 V250 V251 V252 V253 V254 V255
+    }
+}
+
+/// ```lean
+/// structure Fields where
+///   single : Single
+///   wide : Wide
+///   narrow : Narrow
+///   flag : UInt8
+/// ```
+#[derive(LeanStructure)]
+#[lean(num_objs = 1, num_usizes = 0, scalar_size = 4)]
+struct Fields {
+    single: Single,
+    wide: Wide,
+    narrow: Narrow,
+    flag: u8,
+}
+
+#[test]
+fn enum_layout() {
+    assert_eq!(<Single as LeanField>::KIND, FieldKind::Object);
+    assert_eq!(<Wide as LeanField>::KIND, FieldKind::Scalar(2));
+    assert_eq!(<Narrow as LeanField>::KIND, FieldKind::Scalar(1));
+    assert_eq!(
+        <Fields as LeanCtorTypeTag>::LAYOUTS,
+        [CtorLayout::new(1, 0, 4)]
+    );
+
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let fields = Fields {
+                single: Single::Only,
+                wide: Wide::V255,
+                narrow: Narrow::C,
+                flag: 7,
+            }
+            .into_lean(runtime);
+            let ctor = fields.as_ctor().unwrap();
+            let single = ctor.object(unsafe { ObjectField::<AnyTypeTag>::new(0) });
+            assert_eq!(single.object_kind(), ObjectKind::Scalar(0));
+            assert_eq!(ctor.scalar(unsafe { ScalarField::<u16>::new(0) }), 255);
+            assert_eq!(ctor.scalar(unsafe { ScalarField::<u8>::new(2) }), 2);
+            assert_eq!(ctor.scalar(unsafe { ScalarField::<u8>::new(3) }), 7);
+
+            let fields = Fields::from_lean(fields.borrow());
+            assert_eq!(fields.single, Single::Only);
+            assert_eq!(fields.wide, Wide::V255);
+            assert_eq!(fields.narrow, Narrow::C);
+            assert_eq!(fields.flag, 7);
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
@@ -0,0 +1,71 @@
+use std::collections::{BTreeSet, HashMap};
+use std::convert::Infallible;
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{
+        array::{F64Array, U32Array},
+        byte_array::ByteArray,
+        float_array::FloatArray,
+        string::LeanString,
+    },
+};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+#[test]
+fn eq_ord_hash() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let mut cache = HashMap::new();
+            cache.insert(LeanString::from_str(runtime, "λ"), 1);
+            cache.insert(LeanString::from_str(runtime, "a\0b"), 2);
+            assert_eq!(cache.get(&LeanString::from_str(runtime, "λ")), Some(&1));
+            assert_eq!(cache.get(&LeanString::from_str(runtime, "a\0b")), Some(&2));
+            assert_eq!(cache.get(&LeanString::from_str(runtime, "a")), None);
+
+            let strings: BTreeSet<_> = ["b", "", "ab", "λ", "a"]
+                .into_iter()
+                .map(|string| LeanString::from_str(runtime, string))
+                .collect();
+            assert!(
+                strings
+                    .iter()
+                    .map(LeanString::as_str)
+                    .eq(["", "a", "ab", "b", "λ"])
+            );
+
+            let bytes = ByteArray::from_exact_size_iterator(runtime, [1_u8, 2, 3]);
+            assert!(bytes == ByteArray::from_exact_size_iterator(runtime, [1_u8, 2, 3]));
+            assert!(bytes < ByteArray::from_exact_size_iterator(runtime, [1_u8, 3]));
+            assert!(
+                HashMap::from([(bytes, ())])
+                    .contains_key(&ByteArray::from_exact_size_iterator(runtime, [1_u8, 2, 3]))
+            );
+
+            let floats = FloatArray::from_exact_size_iterator(runtime, [1.0, f64::NAN]);
+            assert!(floats != floats);
+            assert!(
+                FloatArray::from_exact_size_iterator(runtime, [-0.0])
+                    == FloatArray::from_exact_size_iterator(runtime, [0.0])
+            );
+
+            let u32s = U32Array::from_exact_size_iterator(runtime, [1_u32, u32::MAX]);
+            assert!(u32s == U32Array::from_exact_size_iterator(runtime, [1_u32, u32::MAX]));
+            assert!(u32s > U32Array::from_exact_size_iterator(runtime, [1_u32]));
+            assert!(
+                HashMap::from([(u32s, ())]).contains_key(&U32Array::from_exact_size_iterator(
+                    runtime,
+                    [1_u32, u32::MAX]
+                ))
+            );
+            assert!(
+                F64Array::from_exact_size_iterator(runtime, [1.0])
+                    < F64Array::from_exact_size_iterator(runtime, [2.0])
+            );
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
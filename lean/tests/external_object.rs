@@ -0,0 +1,95 @@
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{
+        Owner, Reference,
+        closure::LeanFn,
+        external::{ExternalObject, ExternalTypeTag, LeanExternal},
+        object::SharedObject,
+        string::{LeanString, StringTypeTag},
+    },
+};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+struct Connection {
+    name: SharedObject<StringTypeTag>,
+    queries: AtomicUsize,
+    _open: Arc<()>,
+}
+
+impl LeanExternal for Connection {
+    fn for_each_object(&self, visit: &mut dyn FnMut(&dyn Reference)) {
+        visit(&self.name);
+    }
+}
+
+struct Pattern(String);
+
+impl LeanExternal for Pattern {}
+
+#[test]
+fn external_object() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let open = Arc::new(());
+            let connection = ExternalObject::from_value(
+                runtime,
+                Connection {
+                    name: LeanString::from_str(runtime, "main").into_shared(),
+                    queries: AtomicUsize::new(0),
+                    _open: Arc::clone(&open),
+                },
+            );
+            assert_eq!(connection.get().name.get(runtime), "main");
+
+            // Lean code passes the handle back to Rust
+            let query: LeanFn<(ExternalObject<Connection>,), usize> =
+                LeanFn::from_fn(runtime, |connection: ExternalObject<Connection>| {
+                    connection.get().queries.fetch_add(1, Ordering::Relaxed) + 1
+                });
+            assert_eq!(query.call((connection.share(),)), 1);
+            assert_eq!(query.call((connection.share(),)), 2);
+            assert_eq!(connection.get().queries.load(Ordering::Relaxed), 2);
+
+            let pattern = ExternalObject::from_value(runtime, Pattern("a+b".to_owned()));
+            assert_eq!(pattern.get().0, "a+b");
+
+            // Each Rust type has its own external class
+            let connection = connection.into_any();
+            assert!(connection.is::<ExternalTypeTag<Connection>>());
+            assert!(!connection.is::<ExternalTypeTag<Pattern>>());
+            assert!(!pattern.into_any().is::<ExternalTypeTag<Connection>>());
+
+            let connection = connection
+                .downcast::<ExternalTypeTag<Connection>>()
+                .ok()
+                .unwrap();
+            assert_eq!(connection.get().queries.load(Ordering::Relaxed), 2);
+
+            // Marking a handle as persistent also marks the objects it holds
+            let cache = ExternalObject::from_value(
+                runtime,
+                Connection {
+                    name: LeanString::from_str(runtime, "cache").into_shared(),
+                    queries: AtomicUsize::new(0),
+                    _open: Arc::new(()),
+                },
+            )
+            .into_persistent();
+            assert!(cache.get(runtime).get().name.get(runtime).is_persistent());
+
+            // The Rust value is dropped with the last reference to the handle
+            assert_eq!(Arc::strong_count(&open), 2);
+            drop(connection);
+            assert_eq!(Arc::strong_count(&open), 1);
+
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
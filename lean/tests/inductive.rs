@@ -0,0 +1,115 @@
+use std::borrow::Borrow;
+use std::convert::Infallible;
+use std::ffi::CString;
+
+use lean::{
+    LeanInductive, LeanStructure, MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{
+        ctor::{LeanInductive, LeanStructure, ScalarField},
+        nat::LeanNat,
+        object::Object,
+        string::LeanString,
+    },
+};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+/// ```lean
+/// inductive Status where
+///   | ok
+///   | warning
+///   | error
+/// ```
+#[derive(LeanInductive, Clone, Copy, Debug, PartialEq, Eq)]
+enum Status {
+    Ok,
+    Warning,
+    Error,
+}
+
+/// ```lean
+/// inductive Expr where
+///   | num (value : Nat)
+///   | var (name : String) (index : UInt32)
+///   | add (lhs rhs : Expr)
+///   | hole
+/// ```
+#[derive(LeanInductive)]
+enum Expr<'r> {
+    Num(LeanNat<'r>),
+    #[lean(num_objs = 1, num_usizes = 0, scalar_size = 4)]
+    Var {
+        #[lean(index = 0)]
+        name: LeanString<'r>,
+        #[lean(offset = 0)]
+        index: u32,
+    },
+    Add(Object<'r, Expr<'r>>, Object<'r, Expr<'r>>),
+    Hole,
+}
+
+/// ```lean
+/// structure Report where
+///   status : Status
+///   expr : Expr
+/// ```
+#[derive(LeanStructure)]
+#[lean(num_objs = 1, num_usizes = 0, scalar_size = 1)]
+struct Report<'r> {
+    status: Status,
+    expr: Expr<'r>,
+}
+
+#[test]
+fn inductive() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let lean_status = Status::Warning.into_lean(runtime);
+            assert_eq!(lean_status.ctor_tag(), 1);
+            assert!(lean_status.as_ctor().is_none());
+            assert_eq!(Status::from_lean(lean_status.borrow()), Status::Warning);
+
+            let var = Expr::Var {
+                name: LeanString::from_cstr(runtime, CString::new("x").unwrap()),
+                index: 3,
+            }
+            .into_lean(runtime);
+            let expr = Expr::Add(
+                Expr::Num(LeanNat::from_u64(runtime, 1)).into_lean(runtime),
+                var,
+            )
+            .into_lean(runtime);
+            assert_eq!(expr.ctor_tag(), 2);
+            let ExprView::Add(lhs, rhs) = Expr::view(expr.borrow()) else {
+                panic!("expected `Expr.add`");
+            };
+            assert!(matches!(Expr::view(lhs), ExprView::Num(value) if value.to_u64() == Some(1)));
+            match Expr::view(rhs) {
+                ExprView::Var { name, index } => {
+                    assert_eq!(name.as_cstr(), c"x");
+                    assert_eq!(index, 3);
+                }
+                _ => panic!("expected `Expr.var`"),
+            }
+
+            let hole = Expr::Hole.into_lean(runtime);
+            assert_eq!(hole.ctor_tag(), 3);
+            assert!(matches!(Expr::from_lean(hole.borrow()), Expr::Hole));
+
+            let report = Report {
+                status: Status::Error,
+                expr: Expr::from_lean(expr.borrow()),
+            }
+            .into_lean(runtime);
+            let ctor = report.as_ctor().unwrap();
+            assert_eq!(unsafe { ctor.scalar(ScalarField::<u8>::new(0)) }, 2);
+            let report = Report::from_lean(report.borrow());
+            assert_eq!(report.status, Status::Error);
+            assert!(matches!(report.expr, Expr::Add(..)));
+
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
@@ -0,0 +1,72 @@
+use std::convert::Infallible;
+
+use lean::{
+    LeanIoErrorKind, MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::string::LeanString,
+};
+use lean_sys::{
+    lean_box_uint32, lean_dec, lean_io_result_mk_error, lean_io_result_mk_ok,
+    lean_mk_io_user_error, lean_mk_string, lean_obj_arg, lean_obj_res, lean_unbox_uint32,
+};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+/// Compiled version of `fun (x : UInt32) : IO UInt32 => pure (x + 1)`
+extern "C" fn increment(x: u32, world: lean_obj_arg) -> lean_obj_res {
+    unsafe {
+        lean_dec(world);
+        lean_io_result_mk_ok(lean_box_uint32(x.wrapping_add(1)))
+    }
+}
+
+/// Compiled version of `fun : IO Unit => throw (IO.userError "failure")`
+extern "C" fn fail(world: lean_obj_arg) -> lean_obj_res {
+    unsafe {
+        lean_dec(world);
+        lean_io_result_mk_error(lean_mk_io_user_error(lean_mk_string(c"failure".as_ptr())))
+    }
+}
+
+/// Compiled version of `fun (x : UInt32) : EIO String UInt32 => throw "odd"`
+/// for odd `x` and `pure x` otherwise
+extern "C" fn only_even(x: lean_obj_arg, world: lean_obj_arg) -> lean_obj_res {
+    unsafe {
+        lean_dec(world);
+        if lean_unbox_uint32(x).is_multiple_of(2) {
+            lean_io_result_mk_ok(x)
+        } else {
+            lean_io_result_mk_error(lean_mk_string(c"odd".as_ptr()))
+        }
+    }
+}
+
+#[test]
+fn io() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let value: u32 = unsafe { runtime.run_io(|world| increment(41, world)) }.unwrap();
+            assert_eq!(value, 42);
+
+            let error = unsafe { runtime.run_io::<(), _>(|world| fail(world)) }.unwrap_err();
+            assert_eq!(
+                error.kind(),
+                LeanIoErrorKind::UserError {
+                    message: "failure".into()
+                }
+            );
+
+            let value: Result<u32, LeanString> =
+                unsafe { runtime.run_eio(|world| only_even(lean_box_uint32(4), world)) };
+            assert_eq!(value.ok(), Some(4));
+
+            let error = unsafe {
+                runtime.run_eio::<LeanString, u32, _>(|world| only_even(lean_box_uint32(5), world))
+            }
+            .unwrap_err();
+            assert_eq!(error.as_cstr(), c"odd");
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
@@ -0,0 +1,65 @@
+use std::convert::Infallible;
+use std::io;
+
+use lean::{
+    LeanIoError, LeanIoErrorKind, MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{Owner, Reference, io_error::IoErrorTypeTag, object::Object, string::LeanString},
+};
+use lean_sys::{lean_dec, lean_decode_io_error, lean_obj_arg, lean_obj_res};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+const ENOENT: i32 = 2;
+const EACCES: i32 = 13;
+
+#[test]
+fn io_error() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let filename = LeanString::from_cstr(runtime, c"missing.txt");
+
+            let not_found: Object<IoErrorTypeTag> =
+                unsafe { Object::new(lean_decode_io_error(ENOENT, filename.as_mut_raw())) };
+            let LeanIoErrorKind::NoFileOrDirectory {
+                filename: ref name,
+                os_code,
+                ..
+            } = not_found.kind()
+            else {
+                panic!("unexpected error: {not_found:?}");
+            };
+            assert_eq!(name, "missing.txt");
+            assert_eq!(os_code, ENOENT as u32);
+            assert_eq!(not_found.io_error_kind(), io::ErrorKind::NotFound);
+
+            let denied: LeanIoError =
+                unsafe { Object::new(lean_decode_io_error(EACCES, filename.as_mut_raw())) }
+                    .to_error();
+            assert!(matches!(
+                denied.kind(),
+                LeanIoErrorKind::PermissionDenied { .. }
+            ));
+            assert_eq!(denied.kind().filename(), Some("missing.txt"));
+            assert_eq!(denied.kind().os_code(), Some(EACCES as u32));
+            assert_eq!(denied.io_error_kind(), io::ErrorKind::PermissionDenied);
+            assert!(denied.message().contains("missing.txt"));
+            assert_eq!(
+                io::Error::from(denied).kind(),
+                io::ErrorKind::PermissionDenied
+            );
+
+            // Errors can be re-raised from Rust
+            let reraised = unsafe {
+                runtime.run_io::<(), _>(|world: lean_obj_arg| -> lean_obj_res {
+                    lean_dec(world);
+                    not_found.into_io_result()
+                })
+            }
+            .unwrap_err();
+            assert_eq!(reraised.io_error_kind(), io::ErrorKind::NotFound);
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
@@ -3,7 +3,7 @@ use std::error::Error;
 use std::ffi::CString;
 use std::str::FromStr;
 
-use lean::{LeanError, LeanIoError, MimallocAllocator, MinimalComponents, Modules, Runtime};
+use lean::{LeanError, LeanIoErrorKind, MimallocAllocator, MinimalComponents, Modules, Runtime};
 use lean_sys::{
     lean_io_result_mk_error, lean_mk_io_user_error, lean_mk_string, lean_obj_arg, lean_obj_res,
 };
@@ -39,11 +39,16 @@ fn module_initialization_error() {
     )
     .unwrap_err();
 
-    let cstring = CString::from_str(TestModule::ERROR_MESSAGE).unwrap();
+    let LeanError::ModulesInitialization(io_error) = &error else {
+        panic!("unexpected error: {error:?}");
+    };
     assert_eq!(
-        error,
-        LeanError::ModulesInitialization(LeanIoError(cstring))
+        io_error.kind(),
+        &LeanIoErrorKind::UserError {
+            message: TestModule::ERROR_MESSAGE.into()
+        }
     );
+    assert_eq!(io_error.message(), TestModule::ERROR_MESSAGE);
     assert_eq!(&format!("{error}"), "Lean modules initialization error");
     assert_eq!(
         &format!("{}", error.source().unwrap()),
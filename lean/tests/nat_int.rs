@@ -0,0 +1,63 @@
+use std::convert::Infallible;
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{int::LeanInt, nat::LeanNat},
+};
+use num_bigint::{BigInt, BigUint};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+#[test]
+fn nat_int() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let small = LeanNat::from_u64(runtime, 7);
+            let large = LeanNat::from_u64(runtime, u64::MAX);
+            assert!(small.is_scalar());
+            assert!(!large.is_scalar());
+
+            let sum = &small + &large;
+            assert_eq!(sum.to_u64(), None);
+            assert_eq!(sum.to_biguint(), BigUint::from(u64::MAX) + 7_u32);
+            assert_eq!((&sum - &large).to_u64(), Some(7));
+            assert_eq!((&small - &large).to_u64(), Some(0));
+            assert!(small < large);
+
+            let huge = BigUint::from(3_u32).pow(100);
+            let lean_huge = LeanNat::from_biguint(runtime, &huge);
+            assert_eq!(lean_huge.to_biguint(), huge);
+            assert_eq!(lean_huge.to_string(), huge.to_string());
+            assert_eq!((&lean_huge % &small).to_biguint(), &huge % 7_u32);
+
+            let negative = LeanInt::from_i64(runtime, -5);
+            let minimum = LeanInt::from_i64(runtime, i64::MIN);
+            assert!(negative.is_negative());
+            assert_eq!(minimum.to_i64(), Some(i64::MIN));
+            assert_eq!((&minimum + &negative).to_i64(), None);
+            assert_eq!(
+                (&minimum + &negative).to_bigint(),
+                BigInt::from(i64::MIN) - 5
+            );
+            assert_eq!(
+                (&negative / &LeanInt::from_i64(runtime, 2)).to_i64(),
+                Some(-2)
+            );
+            assert_eq!(
+                negative.div_euclid(&LeanInt::from_i64(runtime, 2)).to_i64(),
+                Some(-3)
+            );
+            assert_eq!((-negative).nat_abs().to_u64(), Some(5));
+
+            let big_negative = -BigInt::from(huge);
+            assert_eq!(
+                LeanInt::from_bigint(runtime, &big_negative).to_bigint(),
+                big_negative
+            );
+
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
@@ -0,0 +1,48 @@
+use std::borrow::Borrow;
+use std::convert::Infallible;
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{
+        Borrower, Reference,
+        object::{Obj, ObjRef},
+        string::{LeanString, StringTypeTag},
+    },
+};
+use lean_sys::b_lean_obj_arg;
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+/// An FFI callback that borrows its argument
+extern "C" fn byte_len(string: b_lean_obj_arg) -> usize {
+    let string: &Obj<'_, StringTypeTag> = unsafe { Obj::new(&string) };
+    string.len()
+}
+
+fn longest<'a>(a: ObjRef<'a, '_, StringTypeTag>, b: ObjRef<'a, '_, StringTypeTag>) -> &'a str {
+    if a.len() >= b.len() {
+        a.as_str()
+    } else {
+        b.as_str()
+    }
+}
+
+#[test]
+fn obj_ref() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let short = LeanString::from_str(runtime, "λ");
+            let long = LeanString::from_str(runtime, "Grüße");
+            assert_eq!(byte_len(unsafe { long.as_mut_raw() }), long.len());
+            assert_eq!(longest(short.borrow(), long.borrow()), "Grüße");
+
+            let borrowed: ObjRef<'_, '_, StringTypeTag> = short.borrow();
+            let owned = borrowed.to_owned();
+            drop(short);
+            assert_eq!(owned, "λ");
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
@@ -0,0 +1,59 @@
+use std::convert::Infallible;
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{
+        CollectIntoLean,
+        array::{ObjectArray, ObjectArrayTypeTag, U32Array, U32ArrayTypeTag},
+        string::{LeanString, StringTypeTag},
+    },
+};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+#[test]
+fn object_array() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let words = ["alpha", "beta", "gamma"];
+            let mut strings: ObjectArray<StringTypeTag> = words
+                .iter()
+                .map(|word| LeanString::from_str(runtime, word))
+                .collect_into_lean(runtime);
+            assert!(strings.iter().map(|string| string.as_str()).eq(words));
+
+            let shared = strings.iter().next().unwrap().to_owned();
+            let mut strings_mut = strings.make_mut();
+            strings_mut.set(0, LeanString::from_str(runtime, "delta"));
+            let last = strings_mut.pop().unwrap();
+            assert_eq!(last, "gamma");
+            assert_eq!(shared, "alpha");
+            assert!(
+                strings
+                    .iter()
+                    .map(|string| string.as_str())
+                    .eq(["delta", "beta"])
+            );
+
+            let nested = ObjectArray::<U32ArrayTypeTag>::from_exact_size_iterator(
+                runtime,
+                [
+                    U32Array::from_exact_size_iterator(runtime, [1_u32, 2]),
+                    U32Array::from_exact_size_iterator(runtime, [0_u32; 0]),
+                ],
+            );
+            let sums: Vec<u32> = nested.iter().map(|array| array.iter().sum()).collect();
+            assert_eq!(sums, [3, 0]);
+
+            let nested_strings =
+                ObjectArray::<ObjectArrayTypeTag<StringTypeTag>>::from_exact_size_iterator(
+                    runtime,
+                    [strings],
+                );
+            assert_eq!(nested_strings.iter().next().unwrap().iter().len(), 2);
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
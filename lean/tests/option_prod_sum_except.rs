@@ -0,0 +1,69 @@
+use std::convert::Infallible;
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{
+        array::{F64ArrayTypeTag, Integer32ArrayTypeTag, U32ArrayTypeTag, U64ArrayTypeTag},
+        except::LeanExcept,
+        option::{LeanOption, OptionTypeTag},
+        prod::{LeanProd, ProdTypeTag},
+        sum::{LeanSum, Sum},
+    },
+};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+#[test]
+fn option_prod_sum_except() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let none = LeanOption::<U32ArrayTypeTag>::from_option(runtime, None::<u32>);
+            let some = LeanOption::<U32ArrayTypeTag>::from_option(runtime, Some(5_u32));
+            assert!(none.is_none());
+            assert_eq!(none.get(), None);
+            assert!(some.is_some());
+            assert_eq!(some.get(), Some(5));
+
+            let nested = LeanOption::<OptionTypeTag<U64ArrayTypeTag>>::from_option(
+                runtime,
+                Some(Some(u64::MAX)),
+            );
+            assert_eq!(nested.get(), Some(Some(u64::MAX)));
+
+            let triple = LeanProd::<
+                Integer32ArrayTypeTag<i32>,
+                ProdTypeTag<F64ArrayTypeTag, U32ArrayTypeTag>,
+            >::from_tuple(runtime, (-1, (0.5, 2_u32)));
+            assert_eq!(triple.fst(), -1);
+            assert_eq!(triple.snd(), (0.5, 2));
+
+            let inl = LeanSum::<U32ArrayTypeTag, F64ArrayTypeTag>::from_sum(
+                runtime,
+                Sum::Inl::<_, f64>(3_u32),
+            );
+            let inr = LeanSum::<U32ArrayTypeTag, F64ArrayTypeTag>::from_sum(
+                runtime,
+                Sum::Inr::<u32, _>(1.5),
+            );
+            assert_eq!(inl.get(), Sum::Inl(3));
+            assert_eq!(inr.get(), Sum::Inr(1.5));
+
+            let ok = LeanExcept::<U32ArrayTypeTag, U64ArrayTypeTag>::from_result(
+                runtime,
+                Ok::<_, u32>(7_u64),
+            );
+            let error = LeanExcept::<U32ArrayTypeTag, U64ArrayTypeTag>::from_result(
+                runtime,
+                Err::<u64, _>(9_u32),
+            );
+            assert!(ok.is_ok());
+            assert_eq!(ok.get(), Ok(7));
+            assert!(error.is_error());
+            assert_eq!(error.get(), Err(9));
+
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
@@ -0,0 +1,55 @@
+use std::convert::Infallible;
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{
+        object::DetachedObject,
+        promise::LeanPromise,
+        string::{LeanString, StringTypeTag},
+    },
+};
+
+mod common;
+
+use common::block_on;
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+#[test]
+fn promise() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let promise: LeanPromise<u64> = LeanPromise::new_promise(runtime);
+            let result = promise.result();
+            assert!(!result.has_finished());
+            promise.resolve(7);
+            promise.resolve(8);
+            assert_eq!(result.get(), Some(7));
+
+            // Another thread resolves the promise while the result is awaited
+            let promise: LeanPromise<DetachedObject<StringTypeTag>> =
+                LeanPromise::new_promise(runtime);
+            let result = promise.result();
+            let promise = promise.into_shared();
+            let resolver = lean::run_in_thread_with_lean_runtime(
+                runtime,
+                move |runtime: &Runtime<MinimalComponents, NoModules>| {
+                    let completion = LeanString::from_str(runtime, "done").detach();
+                    promise.get(runtime).resolve(completion);
+                },
+            );
+            let completion = block_on(result).unwrap();
+            assert_eq!(completion.attach(runtime), "done");
+            resolver.join().unwrap();
+
+            // Dropping an unresolved promise finishes its result with `None`
+            let promise: LeanPromise<u64> = LeanPromise::new_promise(runtime);
+            let result = promise.result();
+            drop(promise);
+            assert_eq!(result.get(), None);
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
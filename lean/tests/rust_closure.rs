@@ -0,0 +1,54 @@
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{Owner, closure::LeanFn, nat::LeanNat},
+};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+#[test]
+fn rust_closure() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let offset = 5;
+            let multiply_add: LeanFn<(u32, u32), u32> =
+                LeanFn::from_fn(runtime, move |a: u32, b: u32| a * b + offset);
+            assert_eq!(multiply_add.arity(), 3);
+            assert_eq!(multiply_add.num_fixed(), 1);
+            assert_eq!(multiply_add.call((6, 7)), 47);
+            assert_eq!(multiply_add.apply_first(2).call((10,)), 25);
+
+            let double: LeanFn<(LeanNat,), LeanNat> = LeanFn::from_fn(runtime, |n| &n + &n);
+            assert_eq!(
+                double.call((LeanNat::from_u64(runtime, 21),)).to_u64(),
+                Some(42)
+            );
+
+            let calls = Arc::new(AtomicUsize::new(0));
+            let mut total = 0_u64;
+            let counted_calls = Arc::clone(&calls);
+            let accumulate: LeanFn<(u64,), u64> = LeanFn::from_fn_mut(runtime, move |x: u64| {
+                counted_calls.fetch_add(1, Ordering::Relaxed);
+                total += x;
+                total
+            });
+            assert_eq!(accumulate.call((1,)), 1);
+            assert_eq!(accumulate.call((u64::MAX - 1,)), u64::MAX);
+            assert_eq!(calls.load(Ordering::Relaxed), 2);
+
+            // The Rust state is dropped with the last reference to the closure
+            let shared = accumulate.share();
+            drop(accumulate);
+            assert_eq!(Arc::strong_count(&calls), 2);
+            drop(shared);
+            assert_eq!(Arc::strong_count(&calls), 1);
+
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
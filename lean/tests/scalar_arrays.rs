@@ -0,0 +1,79 @@
+use std::convert::Infallible;
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{
+        Owner,
+        array::{
+            BoolArray, CharArray, Integer8Array, Integer16Array, Integer32Array, Integer64Array,
+            IsizeArray, U8Array, U16Array,
+        },
+    },
+};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+#[test]
+fn scalar_arrays() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let u8s = [0, 1, u8::MAX];
+            assert!(
+                U8Array::from_exact_size_iterator(runtime, u8s)
+                    .iter()
+                    .eq(u8s)
+            );
+            let i8s = [i8::MIN, -1, 0, i8::MAX];
+            assert!(
+                Integer8Array::from_exact_size_iterator(runtime, i8s)
+                    .iter()
+                    .eq(i8s)
+            );
+            let u16s = [0, u16::MAX];
+            assert!(
+                U16Array::from_exact_size_iterator(runtime, u16s)
+                    .iter()
+                    .eq(u16s)
+            );
+            let i16s = [i16::MIN, -1, i16::MAX];
+            assert!(
+                Integer16Array::from_exact_size_iterator(runtime, i16s)
+                    .iter()
+                    .eq(i16s)
+            );
+            let i64s = [i64::MIN, -1, 0, i64::MAX];
+            assert!(
+                Integer64Array::from_exact_size_iterator(runtime, i64s)
+                    .iter()
+                    .eq(i64s)
+            );
+            let isizes = [isize::MIN, -1, isize::MAX];
+            assert!(
+                IsizeArray::from_exact_size_iterator(runtime, isizes)
+                    .iter()
+                    .eq(isizes)
+            );
+            let bools = [true, false];
+            assert!(
+                BoolArray::from_exact_size_iterator(runtime, bools)
+                    .iter()
+                    .eq(bools)
+            );
+            let chars = ['a', 'λ', '\u{10FFFF}'];
+            assert!(
+                CharArray::from_exact_size_iterator(runtime, chars)
+                    .iter()
+                    .eq(chars)
+            );
+
+            let i32s = Integer32Array::<i32>::from_exact_size_iterator(runtime, [-1, 1 << 20]);
+            let i16s = unsafe { Integer32Array::<i16>::new(i32s.into_raw()) };
+            let results: Vec<_> = i16s.try_iter().collect();
+            assert_eq!(results[0], Ok(-1));
+            assert!(results[1].is_err());
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
@@ -0,0 +1,59 @@
+use std::convert::Infallible;
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{
+        array::U32Array,
+        object::SharedObject,
+        string::{LeanString, StringTypeTag},
+    },
+};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+#[test]
+fn shared_object() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let table = U32Array::from_exact_size_iterator(runtime, (0..1000_u32).map(|i| i * i))
+                .into_persistent();
+            assert!(table.get(runtime).is_persistent());
+            let name: SharedObject<StringTypeTag> =
+                LeanString::from_str(runtime, "squares").into_shared();
+
+            let workers: Vec<_> = (0..4)
+                .map(|worker| {
+                    let table = table.clone();
+                    let name = name.clone();
+                    lean::run_in_thread_with_lean_runtime(
+                        runtime,
+                        move |runtime: &Runtime<MinimalComponents, NoModules>| {
+                            let sum: u64 = table
+                                .get(runtime)
+                                .iter()
+                                .skip(worker * 250)
+                                .take(250)
+                                .map(u64::from)
+                                .sum();
+                            assert_eq!(name.to_object(runtime), "squares");
+                            sum
+                        },
+                    )
+                })
+                .collect();
+            let total: u64 = workers
+                .into_iter()
+                .map(|worker| worker.join().unwrap())
+                .sum();
+            assert_eq!(total, (0..1000_u64).map(|i| i * i).sum());
+
+            // Multi-threaded objects are never exclusive, so they are copied
+            // before being mutated
+            drop(table);
+            assert!(!name.to_object(runtime).is_exclusive());
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
@@ -0,0 +1,59 @@
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{
+        external::{ExternalObject, ExternalTypeTag, LeanExternal},
+        object::SharedObject,
+    },
+};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+struct Handle {
+    _open: Arc<()>,
+}
+
+impl LeanExternal for Handle {}
+
+static TABLE: Mutex<Vec<SharedObject<ExternalTypeTag<Handle>>>> = Mutex::new(Vec::new());
+
+#[test]
+fn shared_object_outlives_runtime() {
+    let dropped_elsewhere = Arc::new(());
+    let stored = Arc::new(());
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            // Threads that are not initialized for Lean leave the object to be
+            // freed by a thread that has a runtime
+            let handle = ExternalObject::from_value(
+                runtime,
+                Handle {
+                    _open: Arc::clone(&dropped_elsewhere),
+                },
+            )
+            .into_shared();
+            std::thread::spawn(move || drop(handle)).join().unwrap();
+
+            let handle = ExternalObject::from_value(
+                runtime,
+                Handle {
+                    _open: Arc::clone(&stored),
+                },
+            )
+            .into_shared();
+            TABLE.lock().unwrap().push(handle);
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+    assert_eq!(Arc::strong_count(&dropped_elsewhere), 1);
+
+    // The runtime has been finalized, so the object is leaked
+    let handle = TABLE.lock().unwrap().pop().unwrap();
+    drop(handle.clone());
+    drop(handle);
+    assert_eq!(Arc::strong_count(&stored), 2);
+}
@@ -0,0 +1,51 @@
+use std::convert::Infallible;
+use std::future::{Future, IntoFuture};
+use std::pin::pin;
+use std::sync::mpsc;
+use std::task::{Context, Waker};
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{string::LeanString, task::TaskPriority},
+};
+
+mod common;
+
+use common::block_on;
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+#[test]
+fn spawn_task() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let sum = runtime.spawn_task(
+                |_runtime: &Runtime<MinimalComponents, NoModules>| (1..=100_u64).sum::<u64>(),
+                TaskPriority::DEFAULT,
+            );
+            assert_eq!(sum.get(), 5050);
+
+            // The task waits until it is released, so it is still running when
+            // it is first polled
+            let (release, released) = mpsc::channel::<()>();
+            let greeting = runtime.spawn_task(
+                move |runtime: &Runtime<MinimalComponents, NoModules>| {
+                    released.recv().unwrap();
+                    LeanString::from_str(runtime, "hello from a task").detach()
+                },
+                TaskPriority::DEDICATED,
+            );
+            let mut greeting = pin!(greeting.into_future());
+            let mut cx = Context::from_waker(Waker::noop());
+            assert!(greeting.as_mut().poll(&mut cx).is_pending());
+            release.send(()).unwrap();
+            assert_eq!(block_on(greeting).attach(runtime), "hello from a task");
+
+            assert_eq!(TaskPriority::new(8), Some(TaskPriority::MAX));
+            assert_eq!(TaskPriority::new(9), None);
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
@@ -0,0 +1,33 @@
+use std::convert::Infallible;
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime, lean_types::string::LeanString,
+};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+#[test]
+fn string() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let text = "Grüße\0aus λ";
+            let string = LeanString::from_str(runtime, text);
+            assert_eq!(string.as_str(), text);
+            assert_eq!(string, text);
+            assert_eq!(string.len(), text.len());
+            assert_eq!(string.char_count(), text.chars().count());
+            assert!(string.chars().eq(text.chars()));
+            assert_eq!(string.as_cstr(), c"Grüße");
+            assert_eq!(format!("{string}"), text);
+            assert_eq!(format!("{string:?}"), format!("{text:?}"));
+
+            let empty = LeanString::from_str(runtime, "");
+            assert!(empty.is_empty());
+            assert_eq!(empty.char_count(), 0);
+            assert_eq!(String::from(empty), "");
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
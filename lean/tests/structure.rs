@@ -0,0 +1,103 @@
+use std::borrow::Borrow;
+use std::convert::Infallible;
+use std::ffi::CString;
+
+use lean::{
+    LeanStructure, MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{
+        ctor::{LeanStructure, ObjectField, ScalarField, UsizeField},
+        string::LeanString,
+    },
+};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+/// ```lean
+/// structure Point where
+///   x : Float
+///   y : Float
+/// ```
+#[derive(LeanStructure, Clone, Copy, Debug, PartialEq)]
+#[lean(num_objs = 0, num_usizes = 0, scalar_size = 16)]
+struct Point {
+    #[lean(offset = 0)]
+    x: f64,
+    #[lean(offset = 8)]
+    y: f64,
+}
+
+/// ```lean
+/// structure Entry where
+///   flag : Bool
+///   name : String
+///   count : UInt32
+///   size : USize
+///   total : UInt64
+///   small : UInt16
+///   origin : Point
+/// ```
+#[derive(LeanStructure)]
+#[lean(num_objs = 2, num_usizes = 1, scalar_size = 15)]
+struct Entry<'r> {
+    #[lean(offset = 14)]
+    flag: bool,
+    #[lean(index = 0)]
+    name: LeanString<'r>,
+    #[lean(offset = 8)]
+    count: u32,
+    #[lean(index = 0)]
+    size: usize,
+    #[lean(offset = 0)]
+    total: u64,
+    #[lean(offset = 12)]
+    small: u16,
+    #[lean(index = 1)]
+    origin: Point,
+}
+
+#[test]
+fn structure() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let origin = Point { x: 1.5, y: -2.0 };
+            let entry = Entry {
+                flag: true,
+                name: LeanString::from_cstr(runtime, CString::new("entry").unwrap()),
+                count: 7,
+                size: 42,
+                total: u64::MAX,
+                small: 3,
+                origin,
+            };
+            let lean_entry = entry.into_lean(runtime);
+
+            let ctor = lean_entry.as_ctor().unwrap();
+            assert_eq!(ctor.tag(), 0);
+            unsafe {
+                assert_eq!(
+                    ctor.object(ObjectField::new(0)).as_cstr(),
+                    CString::new("entry").unwrap().as_c_str()
+                );
+                assert_eq!(ctor.usize(UsizeField::new(0)), 42);
+                assert_eq!(ctor.scalar(ScalarField::<u64>::new(0)), u64::MAX);
+                assert_eq!(ctor.scalar(ScalarField::<u32>::new(8)), 7);
+                assert_eq!(ctor.scalar(ScalarField::<u16>::new(12)), 3);
+                assert!(ctor.scalar(ScalarField::<bool>::new(14)));
+                assert_eq!(Point::from_lean(ctor.object(ObjectField::new(1))), origin);
+            }
+
+            let entry = Entry::from_lean(lean_entry.borrow());
+            assert!(entry.flag);
+            assert_eq!(entry.name.as_cstr(), c"entry");
+            assert_eq!(entry.count, 7);
+            assert_eq!(entry.size, 42);
+            assert_eq!(entry.total, u64::MAX);
+            assert_eq!(entry.small, 3);
+            assert_eq!(entry.origin, origin);
+
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
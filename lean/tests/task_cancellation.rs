@@ -0,0 +1,66 @@
+use std::convert::Infallible;
+use std::sync::mpsc;
+use std::thread;
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{
+        Owner,
+        task::{CancellationToken, TaskPriority, TaskState},
+    },
+};
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+#[test]
+fn task_cancellation() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            assert!(!runtime.check_canceled());
+
+            let (started, has_started) = mpsc::channel::<()>();
+            let computation = runtime.spawn_task(
+                move |runtime: &Runtime<MinimalComponents, NoModules>| {
+                    started.send(()).unwrap();
+                    let mut iterations = 0_u64;
+                    while !runtime.check_canceled() {
+                        iterations += 1;
+                        thread::yield_now();
+                    }
+                    iterations
+                },
+                TaskPriority::DEDICATED,
+            );
+            let token = CancellationToken::new();
+            token.attach(&computation);
+
+            has_started.recv().unwrap();
+            assert_eq!(computation.state(), TaskState::Running);
+            assert!(!computation.has_finished());
+
+            // A client disconnects on another thread
+            let client = token.clone();
+            thread::spawn(move || client.cancel()).join().unwrap();
+            assert!(token.is_canceled());
+
+            let finished = computation.share();
+            computation.get();
+            assert!(finished.has_finished());
+
+            // Tasks attached after cancellation are cancelled immediately
+            let late = runtime.spawn_task(
+                |runtime: &Runtime<MinimalComponents, NoModules>| {
+                    while !runtime.check_canceled() {
+                        thread::yield_now();
+                    }
+                },
+                TaskPriority::DEDICATED,
+            );
+            token.attach(&late);
+            late.get();
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}
@@ -0,0 +1,51 @@
+use std::convert::Infallible;
+use std::future::{Future, IntoFuture};
+use std::pin::pin;
+use std::task::{Context, Waker};
+
+use lean::{
+    MimallocAllocator, MinimalComponents, NoModules, Runtime,
+    lean_types::{Owner, promise::LeanPromise, string::LeanString, task::LeanTask},
+};
+
+mod common;
+
+use common::block_on;
+
+#[global_allocator]
+static ALLOCATOR: MimallocAllocator = MimallocAllocator {};
+
+#[test]
+fn task_future() {
+    lean::run_in_lean_runtime_with_default_error_handler(
+        |runtime: &Runtime<MinimalComponents, NoModules>| {
+            let answer = LeanTask::pure(runtime, 42_u32);
+            assert_eq!(answer.share().get(), 42);
+            assert_eq!(block_on(answer), 42);
+
+            let greeting: LeanTask<LeanString> =
+                LeanTask::pure(runtime, LeanString::from_str(runtime, "hello"));
+            let borrowed = greeting.share();
+            assert_eq!(block_on(greeting), "hello");
+            assert_eq!(borrowed.get(), "hello");
+
+            // The result of a promise is pending until another thread resolves
+            // it, which wakes the future
+            let promise: LeanPromise<u64> = LeanPromise::new_promise(runtime);
+            let mut result = pin!(promise.result().into_future());
+            let mut cx = Context::from_waker(Waker::noop());
+            assert!(result.as_mut().poll(&mut cx).is_pending());
+            let promise = promise.into_shared();
+            let resolver = lean::run_in_thread_with_lean_runtime(
+                runtime,
+                move |runtime: &Runtime<MinimalComponents, NoModules>| {
+                    promise.get(runtime).resolve(7);
+                },
+            );
+            assert_eq!(block_on(result), Some(7));
+            resolver.join().unwrap();
+            Ok::<_, Infallible>(())
+        },
+    )
+    .unwrap();
+}